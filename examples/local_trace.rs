@@ -0,0 +1,42 @@
+use atlantic_client::{
+    error::AtlanticSdkError,
+    local_trace::LocalTraceGenerator,
+    models::{AtlanticSdk, Layout, ProverVersion},
+};
+use cairo_vm::{vm::errors::hint_errors::HintError, Felt252};
+
+// The Python hint in examples/square.cairo, reimplemented for cairo-vm.
+const READ_N_HINT: &str = r#"memory[ap] = program_input["n"]"#;
+
+// Runs examples/square.cairo locally and only submits the PIE for proving.
+// Reads ATLANTIC_API_KEY and ATLANTIC_PROFILE / ATLANTIC_CONFIG from the environment.
+#[tokio::main]
+pub async fn main() -> Result<(), AtlanticSdkError> {
+    let sdk = AtlanticSdk::from_env()?;
+    let program_file = std::fs::read("examples/compiled_square.json")?;
+    let input_file = std::fs::read("examples/square_input.json")?;
+    let generator = LocalTraceGenerator::new(Layout::Recursive).with_hint(
+        READ_N_HINT,
+        |vm, scopes, _, _, _| {
+            let n = scopes
+                .get_ref::<serde_json::Value>("program_input")?
+                .get("n")
+                .and_then(serde_json::Value::as_u64)
+                .ok_or_else(|| HintError::CustomHint("program_input.n is missing".into()))?;
+            vm.insert_value(vm.get_ap(), Felt252::from(n))?;
+            Ok(())
+        },
+    );
+    let atlantic_query = sdk
+        .prove_program_locally(
+            generator,
+            program_file,
+            input_file,
+            Layout::Recursive,
+            ProverVersion::Starkware,
+            "example",
+        )
+        .await?;
+    println!("{:?}", atlantic_query);
+    Ok(())
+}
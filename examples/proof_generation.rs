@@ -0,0 +1,31 @@
+use std::path::PathBuf;
+
+use atlantic_client::{
+    error::AtlanticSdkError,
+    models::{AtlanticSdk, Layout, ProverVersion},
+};
+use clap::Parser;
+
+#[derive(Parser, Debug, Clone)]
+#[clap(author, version, about, long_about = None)]
+pub struct Args {
+    #[arg(long, short, env, default_value = "auto")]
+    pub layout: Layout,
+    #[arg(long, env, default_value = "starkware_sharp")]
+    pub prover: ProverVersion,
+    #[arg(long, env, default_value = "examples/pie.zip")]
+    pub pie_path: PathBuf,
+}
+
+// Reads ATLANTIC_API_KEY and ATLANTIC_PROFILE / ATLANTIC_CONFIG from the environment.
+#[tokio::main]
+pub async fn main() -> Result<(), AtlanticSdkError> {
+    let args = Args::parse();
+    let sdk = AtlanticSdk::from_env()?;
+    let pie_file = std::fs::read(args.pie_path)?;
+    let atlantic_query = sdk
+        .proof_generation(pie_file, args.layout, args.prover, "example")
+        .await?;
+    println!("{:?}", atlantic_query);
+    Ok(())
+}
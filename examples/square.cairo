@@ -0,0 +1,12 @@
+%builtins output
+
+// Outputs the square of `n` from the program input.
+func main{output_ptr: felt*}() {
+    %{ memory[ap] = program_input["n"] %}
+    ap += 1;
+    let n = [ap - 1];
+    tempvar square = n * n;
+    assert [output_ptr] = square;
+    let output_ptr = output_ptr + 1;
+    return ();
+}
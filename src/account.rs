@@ -0,0 +1,129 @@
+use tracing::info;
+
+use crate::{billing::Spend, error::AtlanticSdkError, models::AtlanticSdk};
+
+// Page size used to walk the query history in `account`.
+const ACCOUNT_PAGE_SIZE: u32 = 100;
+
+// What the API tells about the client behind the key. Remaining credits and plan limits are
+// not exposed by any documented endpoint, so they are not reported here.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Account {
+    // `None` until the client has submitted a query.
+    pub client_id: Option<String>,
+    // Summed over every query the client has submitted.
+    pub usage: Spend,
+}
+
+impl AtlanticSdk {
+    // Walks the whole query history, so this costs one read request per hundred queries.
+    pub async fn account(&self) -> Result<Account, AtlanticSdkError> {
+        let mut account = Account::default();
+        let mut offset = 0;
+        loop {
+            let page = self
+                .get_sharp_queries(Some(ACCOUNT_PAGE_SIZE), Some(offset))
+                .await?;
+            let fetched = page.sharp_queries.len() as u32;
+            for query in &page.sharp_queries {
+                account
+                    .client_id
+                    .get_or_insert_with(|| query.submitted_by_client.clone());
+                account.usage.add(query);
+            }
+            offset += fetched;
+            if fetched < ACCOUNT_PAGE_SIZE || offset as usize >= page.total {
+                break;
+            }
+        }
+        info!(
+            "Client {} used {} credits over {} queries",
+            account.client_id.as_deref().unwrap_or("<none>"),
+            account.usage.credits_used,
+            account.usage.queries
+        );
+        Ok(account)
+    }
+
+    // Lists a single query to check the key, failing with `Unauthorized` when it is rejected.
+    // Returns the client ID (`SharpQueryDetails::submitted_by_client`) when the client has
+    // submitted a query before. The API does not report remaining credits; running out
+    // surfaces as `InsufficientCredits` on the next submission.
+    pub async fn validate_credentials(&self) -> Result<Option<String>, AtlanticSdkError> {
+        let queries = self.get_sharp_queries(Some(1), None).await?;
+        let client_id = queries
+            .sharp_queries
+            .into_iter()
+            .next()
+            .map(|query| query.submitted_by_client);
+        match &client_id {
+            Some(client_id) => info!("Authenticated as client {}", client_id),
+            None => info!("Authenticated; no queries submitted yet"),
+        }
+        Ok(client_id)
+    }
+
+    // Meant to be chained on construction, e.g. `AtlanticSdk::from_env()?.validated().await?`.
+    pub async fn validated(self) -> Result<Self, AtlanticSdkError> {
+        self.validate_credentials().await?;
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{http::StatusCode, routing::get, Router};
+
+    use super::*;
+    use crate::{mock_server, models::tests::SHARP_QUERIES};
+
+    #[tokio::test]
+    async fn test_account() {
+        let sdk = mock_server::sdk(
+            Router::new().route("/v1/atlantic-queries", get(|| async { SHARP_QUERIES })),
+        )
+        .await;
+        let account = sdk.account().await.unwrap();
+        assert_eq!(
+            account.client_id.as_deref(),
+            Some("01J8M351V7NV7QRQYCS54RKFYD")
+        );
+        assert_eq!(account.usage.queries, 2);
+
+        let sdk = mock_server::sdk(Router::new().route(
+            "/v1/atlantic-queries",
+            get(|| async { r#"{"sharpQueries": [], "total": 0}"# }),
+        ))
+        .await;
+        assert_eq!(sdk.account().await.unwrap(), Account::default());
+    }
+
+    #[tokio::test]
+    async fn test_validate_credentials() {
+        let sdk = mock_server::sdk(
+            Router::new().route("/v1/atlantic-queries", get(|| async { SHARP_QUERIES })),
+        )
+        .await;
+        assert_eq!(
+            sdk.validate_credentials().await.unwrap().as_deref(),
+            Some("01J8M351V7NV7QRQYCS54RKFYD")
+        );
+
+        let sdk = mock_server::sdk(Router::new().route(
+            "/v1/atlantic-queries",
+            get(|| async { r#"{"sharpQueries": [], "total": 0}"# }),
+        ))
+        .await;
+        assert_eq!(sdk.validate_credentials().await.unwrap(), None);
+
+        let sdk = mock_server::sdk(Router::new().route(
+            "/v1/atlantic-queries",
+            get(|| async { (StatusCode::UNAUTHORIZED, "invalid api key") }),
+        ))
+        .await;
+        assert!(matches!(
+            sdk.validated().await,
+            Err(AtlanticSdkError::Unauthorized(_))
+        ));
+    }
+}
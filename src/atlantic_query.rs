@@ -0,0 +1,354 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use bytes::Bytes;
+use reqwest::multipart;
+use serde::de::DeserializeOwned;
+use tracing::warn;
+use url::Url;
+
+use crate::{
+    error::AtlanticSdkError,
+    ledger::LedgerEntry,
+    models::{
+        AtlanticSdk, CairoVersion, Chain, FactHashResponse, Layout, Network, ProverVersion,
+        QueryResponse,
+    },
+    rate_limit::EndpointKind,
+};
+
+impl AtlanticSdk {
+    // L1 queries always run on SHARP, so `prover` is only sent for L2.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn atlantic_query(
+        &self,
+        chain: Chain,
+        program_hash: Option<&str>,
+        program_file: Vec<u8>,
+        input_file: Vec<u8>,
+        cairo_version: CairoVersion,
+        prover: ProverVersion,
+        mock_fact_hash: bool,
+        external_id: &str,
+    ) -> Result<QueryResponse, AtlanticSdkError> {
+        let mut form = SubmitForm::new();
+        if let Some(program_hash) = program_hash {
+            form = form.text("programHash", program_hash.to_string());
+        }
+        let mut form = form
+            .part("programFile", cairo_version.program_part(program_file)?)
+            .part(
+                "inputFile",
+                FilePart::new(input_file, "input.json", "application/json"),
+            )
+            .text("cairoVersion", cairo_version.to_string());
+        if chain == Chain::L2 {
+            prover.ensure_known()?;
+            form = form.text("prover", prover.to_string());
+        }
+        let form = form
+            .text("mockFactHash", mock_fact_hash.to_string())
+            .network(self.network)
+            .text("externalId", external_id.to_string());
+        let url = match chain {
+            Chain::L1 => &self.l1.atlantic_query,
+            Chain::L2 => &self.l2.atlantic_query,
+            Chain::Unknown => return Err(unknown_chain()),
+        };
+        self.submit_query(url, form).await
+    }
+
+    // As in `atlantic_query`, `prover` and its layout check only apply to L2.
+    pub async fn proof_generation_verification(
+        &self,
+        chain: Chain,
+        pie_file: Vec<u8>,
+        layout: Layout,
+        prover: ProverVersion,
+        mock_fact_hash: bool,
+        external_id: &str,
+    ) -> Result<QueryResponse, AtlanticSdkError> {
+        layout.ensure_known()?;
+        let mut form = SubmitForm::new()
+            .part(
+                "pieFile",
+                FilePart::new(pie_file, "pie.zip", "application/zip"),
+            )
+            .text("layout", layout.to_string());
+        if chain == Chain::L2 {
+            prover.validate_layout(&layout)?;
+            form = form.text("prover", prover.to_string());
+        }
+        let form = form
+            .text("mockFactHash", mock_fact_hash.to_string())
+            .network(self.network)
+            .text("externalId", external_id.to_string());
+        let url = match chain {
+            Chain::L1 => &self.l1.proof_generation_verification,
+            Chain::L2 => &self.l2.from_proof_generation_to_proof_verification,
+            Chain::Unknown => return Err(unknown_chain()),
+        };
+        self.submit_query(url, form).await
+    }
+
+    pub async fn fact_hash_calculation(
+        &self,
+        chain: Chain,
+        pie_file: Vec<u8>,
+    ) -> Result<FactHashResponse, AtlanticSdkError> {
+        let form = SubmitForm::new().part(
+            "pieFile",
+            FilePart::new(pie_file, "pie.zip", "application/zip"),
+        );
+        let url = match chain {
+            Chain::L1 => &self.l1.fact_hash_calculation,
+            Chain::L2 => &self.l2.fact_hash_calculation,
+            Chain::Unknown => return Err(unknown_chain()),
+        };
+        self.submit(url, form).await
+    }
+
+    pub(crate) async fn submit_query(
+        &self,
+        url: &Url,
+        form: SubmitForm,
+    ) -> Result<QueryResponse, AtlanticSdkError> {
+        // Only query submissions report completion, so only they register the callback.
+        let form = match &self.callback_url {
+            Some(callback_url) => form.text("callbackUrl", callback_url.to_string()),
+            None => form,
+        };
+        let params = form.params.clone();
+        let response: QueryResponse = self.submit(url, form).await?;
+        if let Some(ledger) = &self.ledger {
+            let entry = LedgerEntry::submitted(&response.atlantic_query_id, url.path(), params);
+            if let Err(err) = ledger.record(entry).await {
+                warn!(
+                    "Failed to record sharpQueryId {} in ledger: {}",
+                    response.atlantic_query_id, err
+                );
+            }
+        }
+        Ok(response)
+    }
+
+    // Resends the form up to `rate_limit_retries` times while the API answers 429.
+    pub(crate) async fn submit<T: DeserializeOwned>(
+        &self,
+        url: &Url,
+        form: SubmitForm,
+    ) -> Result<T, AtlanticSdkError> {
+        let mut attempt = 0;
+        loop {
+            match self.send_form(url, &form).await {
+                Err(AtlanticSdkError::RateLimited { retry_after })
+                    if attempt < self.rate_limit_retries =>
+                {
+                    attempt += 1;
+                    let delay = retry_after.unwrap_or(DEFAULT_RETRY_AFTER * 2u32.pow(attempt));
+                    warn!(
+                        "Rate limited, retrying in {:?} (attempt {})",
+                        delay, attempt
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn send_form<T: DeserializeOwned>(
+        &self,
+        url: &Url,
+        form: &SubmitForm,
+    ) -> Result<T, AtlanticSdkError> {
+        self.throttle(EndpointKind::Submit).await;
+        let response = self
+            .client
+            .post(url.clone())
+            .query(&[("apiKey", &self.api_key)])
+            .multipart(form.to_multipart()?)
+            .send()
+            .await?;
+
+        let status = response.status();
+
+        match status {
+            reqwest::StatusCode::CREATED => {
+                let response = response.json::<T>().await?;
+                Ok(response)
+            }
+            _ => Err(AtlanticSdkError::from_response(response).await),
+        }
+    }
+}
+
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+
+fn unknown_chain() -> AtlanticSdkError {
+    AtlanticSdkError::CustomError("Cannot submit a query to an unknown chain".to_string())
+}
+
+// Uploaded file. The bytes are shared, so a rate-limited form is resent without copying them.
+pub(crate) struct FilePart {
+    bytes: Bytes,
+    file_name: &'static str,
+    mime: &'static str,
+}
+
+impl FilePart {
+    pub(crate) fn new(bytes: Vec<u8>, file_name: &'static str, mime: &'static str) -> Self {
+        Self {
+            bytes: Bytes::from(bytes),
+            file_name,
+            mime,
+        }
+    }
+}
+
+enum FormField {
+    Text(String),
+    File(FilePart),
+}
+
+// Multipart form that also keeps its text fields, so submissions can be recorded in the ledger.
+pub(crate) struct SubmitForm {
+    fields: Vec<(String, FormField)>,
+    params: BTreeMap<String, String>,
+}
+
+impl SubmitForm {
+    pub(crate) fn new() -> Self {
+        Self {
+            fields: Vec::new(),
+            params: BTreeMap::new(),
+        }
+    }
+
+    pub(crate) fn text(mut self, name: &str, value: String) -> Self {
+        self.params.insert(name.to_string(), value.clone());
+        self.fields.push((name.to_string(), FormField::Text(value)));
+        self
+    }
+
+    // The network is only sent when configured, otherwise the server picks its default.
+    pub(crate) fn network(self, network: Option<Network>) -> Self {
+        match network {
+            Some(network) => self.text("network", network.to_string()),
+            None => self,
+        }
+    }
+
+    pub(crate) fn part(mut self, name: &str, file: FilePart) -> Self {
+        self.fields.push((name.to_string(), FormField::File(file)));
+        self
+    }
+
+    fn to_multipart(&self) -> Result<multipart::Form, AtlanticSdkError> {
+        let mut form = multipart::Form::new();
+        for (name, field) in &self.fields {
+            form = match field {
+                FormField::Text(value) => form.text(name.clone(), value.clone()),
+                FormField::File(file) => form.part(
+                    name.clone(),
+                    multipart::Part::stream_with_length(
+                        file.bytes.clone(),
+                        file.bytes.len() as u64,
+                    )
+                    .file_name(file.file_name)
+                    .mime_str(file.mime)?,
+                ),
+            };
+        }
+        Ok(form)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::{extract::Multipart, http::StatusCode, routing::post, Router};
+
+    use super::*;
+    use crate::mock_server;
+
+    #[tokio::test]
+    async fn test_callback_url_only_on_queries() {
+        let fields = Arc::new(Mutex::new(Vec::new()));
+        let record = |response: &'static str| {
+            let fields = fields.clone();
+            post(move |mut form: Multipart| async move {
+                let mut names = Vec::new();
+                while let Some(field) = form.next_field().await.unwrap() {
+                    names.push(field.name().unwrap().to_string());
+                }
+                *fields.lock().unwrap() = names;
+                (StatusCode::CREATED, response)
+            })
+        };
+        let sdk = mock_server::sdk(
+            Router::new()
+                .route(
+                    "/v1/proof-generation",
+                    record(r#"{"atlanticQueryId": "01JDKQF9VY2NDBFZAFNFXZC17Z"}"#),
+                )
+                .route(
+                    "/v1/l2/fact-hash-calculation",
+                    record(r#"{"factHash": "0x1"}"#),
+                ),
+        )
+        .await
+        .with_callback_url(Url::parse("https://example.com/atlantic").unwrap());
+
+        sdk.proof_generation(
+            b"pie".to_vec(),
+            Layout::Recursive,
+            ProverVersion::Starkware,
+            "",
+        )
+        .await
+        .unwrap();
+        assert!(fields.lock().unwrap().contains(&"callbackUrl".to_string()));
+
+        sdk.l2_fact_hash_calculation(b"pie".to_vec()).await.unwrap();
+        assert_eq!(*fields.lock().unwrap(), ["pieFile"]);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unknown_values() {
+        // Nothing listens, so any request would fail with a connection error instead.
+        let sdk = AtlanticSdk::new("api_key".to_string(), mock_server::closed_url().await).unwrap();
+        let err = sdk
+            .proof_generation_verification(
+                Chain::L1,
+                b"pie".to_vec(),
+                Layout::Unknown,
+                ProverVersion::Starkware,
+                false,
+                "",
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AtlanticSdkError::InvalidVariant { kind: "layout", .. }
+        ));
+        let err = sdk
+            .atlantic_query(
+                Chain::L2,
+                Some("0x1"),
+                b"{}".to_vec(),
+                b"{}".to_vec(),
+                CairoVersion::Zero,
+                ProverVersion::Unknown,
+                false,
+                "",
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AtlanticSdkError::InvalidVariant { kind: "prover", .. }
+        ));
+    }
+}
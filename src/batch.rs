@@ -0,0 +1,351 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use futures::{stream, StreamExt};
+use tracing::{debug, info, warn};
+
+use crate::{
+    error::AtlanticSdkError,
+    models::{AtlanticSdk, CairoVersion, Chain, Layout, ProverVersion, QueryResponse},
+};
+
+const MAX_RATE_LIMIT_RETRIES: u32 = 5;
+
+// One submission, mirroring the arguments of the matching `AtlanticSdk` method.
+#[derive(Debug, Clone)]
+pub enum SubmitRequest {
+    AtlanticQuery {
+        chain: Chain,
+        program_hash: Option<String>,
+        program_file: Vec<u8>,
+        input_file: Vec<u8>,
+        cairo_version: CairoVersion,
+        prover: ProverVersion,
+        mock_fact_hash: bool,
+        external_id: String,
+    },
+    ProofGenerationVerification {
+        chain: Chain,
+        pie_file: Vec<u8>,
+        layout: Layout,
+        prover: ProverVersion,
+        mock_fact_hash: bool,
+        external_id: String,
+    },
+    ProofVerification {
+        proof_file: Vec<u8>,
+        mock_fact_hash: bool,
+        prover: ProverVersion,
+        external_id: String,
+    },
+    TraceGeneration {
+        program_hash: String,
+        program_file: Vec<u8>,
+        input_file: Vec<u8>,
+        cairo_version: CairoVersion,
+        external_id: String,
+    },
+    TraceGenToProofGen {
+        program_hash: String,
+        program_file: Vec<u8>,
+        input_file: Vec<u8>,
+        cairo_version: CairoVersion,
+        layout: Layout,
+        prover: ProverVersion,
+        external_id: String,
+    },
+    ProofGeneration {
+        pie_file: Vec<u8>,
+        layout: Layout,
+        prover: ProverVersion,
+        external_id: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OnError {
+    #[default]
+    Continue,
+    // Stop starting new submissions; the ones already in flight still complete.
+    Abort,
+}
+
+impl AtlanticSdk {
+    pub async fn submit_request(
+        &self,
+        request: SubmitRequest,
+    ) -> Result<QueryResponse, AtlanticSdkError> {
+        match request {
+            SubmitRequest::AtlanticQuery {
+                chain,
+                program_hash,
+                program_file,
+                input_file,
+                cairo_version,
+                prover,
+                mock_fact_hash,
+                external_id,
+            } => {
+                self.atlantic_query(
+                    chain,
+                    program_hash.as_deref(),
+                    program_file,
+                    input_file,
+                    cairo_version,
+                    prover,
+                    mock_fact_hash,
+                    &external_id,
+                )
+                .await
+            }
+            SubmitRequest::ProofGenerationVerification {
+                chain,
+                pie_file,
+                layout,
+                prover,
+                mock_fact_hash,
+                external_id,
+            } => {
+                self.proof_generation_verification(
+                    chain,
+                    pie_file,
+                    layout,
+                    prover,
+                    mock_fact_hash,
+                    &external_id,
+                )
+                .await
+            }
+            SubmitRequest::ProofVerification {
+                proof_file,
+                mock_fact_hash,
+                prover,
+                external_id,
+            } => {
+                self.l2_proof_verification(proof_file, mock_fact_hash, prover, &external_id)
+                    .await
+            }
+            SubmitRequest::TraceGeneration {
+                program_hash,
+                program_file,
+                input_file,
+                cairo_version,
+                external_id,
+            } => {
+                self.trace_generation(
+                    &program_hash,
+                    program_file,
+                    input_file,
+                    cairo_version,
+                    &external_id,
+                )
+                .await
+            }
+            SubmitRequest::TraceGenToProofGen {
+                program_hash,
+                program_file,
+                input_file,
+                cairo_version,
+                layout,
+                prover,
+                external_id,
+            } => {
+                self.trace_gen_to_proof_gen(
+                    &program_hash,
+                    program_file,
+                    input_file,
+                    cairo_version,
+                    layout,
+                    prover,
+                    &external_id,
+                )
+                .await
+            }
+            SubmitRequest::ProofGeneration {
+                pie_file,
+                layout,
+                prover,
+                external_id,
+            } => {
+                self.proof_generation(pie_file, layout, prover, &external_id)
+                    .await
+            }
+        }
+    }
+
+    // Runs at most `max_in_flight` submissions at a time. Results are returned in request order.
+    // Submissions rejected with 429 are resent up to `MAX_RATE_LIMIT_RETRIES` times.
+    pub async fn submit_batch(
+        &self,
+        requests: impl IntoIterator<Item = SubmitRequest>,
+        max_in_flight: usize,
+        on_error: OnError,
+    ) -> Vec<Result<QueryResponse, AtlanticSdkError>> {
+        let sdk = AtlanticSdk {
+            rate_limit_retries: MAX_RATE_LIMIT_RETRIES,
+            ..self.clone()
+        };
+        let aborted = AtomicBool::new(false);
+        // Requests are pulled lazily as slots free up, so large PIEs are not all held at once.
+        info!("Submitting batch with up to {} in flight", max_in_flight);
+        let mut results: Vec<_> = stream::iter(requests.into_iter().enumerate())
+            .map(|(index, request)| {
+                let (sdk, aborted) = (&sdk, &aborted);
+                async move {
+                    if aborted.load(Ordering::SeqCst) {
+                        return (index, Err(AtlanticSdkError::BatchAborted));
+                    }
+                    let result = sdk.submit_request(request).await;
+                    match &result {
+                        Ok(response) => debug!(
+                            "Batch request {} submitted as {}",
+                            index, response.atlantic_query_id
+                        ),
+                        Err(err) => warn!("Batch request {} failed: {}", index, err),
+                    }
+                    if result.is_err() && on_error == OnError::Abort {
+                        aborted.store(true, Ordering::SeqCst);
+                    }
+                    (index, result)
+                }
+            })
+            .buffer_unordered(max_in_flight.max(1))
+            .collect()
+            .await;
+        info!("Batch of {} requests finished", results.len());
+        results.sort_by_key(|(index, _)| *index);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use axum::{http::StatusCode, routing::post, Router};
+
+    use super::*;
+    use crate::mock_server;
+
+    fn proof_generation(external_id: &str) -> SubmitRequest {
+        SubmitRequest::ProofGeneration {
+            pie_file: b"pie".to_vec(),
+            layout: Layout::Recursive,
+            prover: ProverVersion::Starkware,
+            external_id: external_id.to_string(),
+        }
+    }
+
+    fn trace_generation(external_id: &str) -> SubmitRequest {
+        SubmitRequest::TraceGeneration {
+            program_hash: "0x1".to_string(),
+            program_file: b"{}".to_vec(),
+            input_file: b"{}".to_vec(),
+            cairo_version: CairoVersion::Zero,
+            external_id: external_id.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_keeps_request_order() {
+        let sdk = mock_server::sdk(
+            Router::new()
+                .route(
+                    "/v1/proof-generation",
+                    post(|| async {
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                        (StatusCode::CREATED, r#"{"atlanticQueryId": "slow"}"#)
+                    }),
+                )
+                .route(
+                    "/v1/trace-generation",
+                    post(|| async { (StatusCode::CREATED, r#"{"atlanticQueryId": "fast"}"#) }),
+                ),
+        )
+        .await;
+        let results = sdk
+            .submit_batch(
+                [proof_generation("a"), trace_generation("b")],
+                2,
+                OnError::Continue,
+            )
+            .await;
+        let ids: Vec<_> = results
+            .into_iter()
+            .map(|result| result.unwrap().atlantic_query_id)
+            .collect();
+        assert_eq!(ids, ["slow", "fast"]);
+    }
+
+    #[tokio::test]
+    async fn test_batch_abort_on_first_error() {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        let router = Router::new()
+            .route(
+                "/v1/proof-generation",
+                post(|| async { (StatusCode::INTERNAL_SERVER_ERROR, "boom") }),
+            )
+            .route(
+                "/v1/trace-generation",
+                post(move || async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    (StatusCode::CREATED, r#"{"atlanticQueryId": "ok"}"#)
+                }),
+            );
+        let sdk = mock_server::sdk(router).await;
+        let requests = || {
+            [
+                proof_generation("a"),
+                trace_generation("b"),
+                trace_generation("c"),
+            ]
+        };
+
+        let results = sdk.submit_batch(requests(), 1, OnError::Abort).await;
+        assert!(matches!(results[0], Err(AtlanticSdkError::CustomError(_))));
+        assert!(matches!(results[1], Err(AtlanticSdkError::BatchAborted)));
+        assert!(matches!(results[2], Err(AtlanticSdkError::BatchAborted)));
+        assert_eq!(hits.load(Ordering::SeqCst), 0);
+
+        let results = sdk.submit_batch(requests(), 1, OnError::Continue).await;
+        assert!(results[0].is_err());
+        assert!(results[1].is_ok() && results[2].is_ok());
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_batch_retries_rate_limited() {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        let sdk = mock_server::sdk(Router::new().route(
+            "/v1/proof-generation",
+            post(move || async move {
+                if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                    (
+                        StatusCode::TOO_MANY_REQUESTS,
+                        [("retry-after", "0")],
+                        String::new(),
+                    )
+                } else {
+                    (
+                        StatusCode::CREATED,
+                        [("retry-after", "0")],
+                        r#"{"atlanticQueryId": "ok"}"#.to_string(),
+                    )
+                }
+            }),
+        ))
+        .await;
+        let results = sdk
+            .submit_batch([proof_generation("a")], 1, OnError::Abort)
+            .await;
+        assert_eq!(results[0].as_ref().unwrap().atlantic_query_id, "ok");
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+}
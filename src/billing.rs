@@ -0,0 +1,152 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+use crate::{
+    felt::Felt,
+    models::{Context, Layout, SharpQueryDetails},
+};
+
+// Summed cost of a set of queries.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Spend {
+    pub queries: usize,
+    pub price: Decimal,
+    pub gas_used: u64,
+    pub credits_used: u64,
+    pub trace_credits_used: u64,
+}
+
+impl Spend {
+    pub fn add(&mut self, query: &SharpQueryDetails) {
+        // Counters reported by the API are not trusted to stay small over long histories.
+        self.queries = self.queries.saturating_add(1);
+        self.price = self.price.saturating_add(query.price);
+        self.gas_used = self.gas_used.saturating_add(query.gas_used);
+        self.credits_used = self.credits_used.saturating_add(query.credits_used);
+        self.trace_credits_used = self
+            .trace_credits_used
+            .saturating_add(query.trace_credits_used);
+    }
+}
+
+impl<'a> FromIterator<&'a SharpQueryDetails> for Spend {
+    fn from_iter<I: IntoIterator<Item = &'a SharpQueryDetails>>(queries: I) -> Self {
+        let mut spend = Spend::default();
+        for query in queries {
+            spend.add(query);
+        }
+        spend
+    }
+}
+
+pub fn total_spend(queries: &[SharpQueryDetails]) -> Spend {
+    queries.iter().collect()
+}
+
+pub fn spend_by<K: Ord>(
+    queries: &[SharpQueryDetails],
+    key: impl Fn(&SharpQueryDetails) -> K,
+) -> BTreeMap<K, Spend> {
+    let mut groups: BTreeMap<K, Spend> = BTreeMap::new();
+    for query in queries {
+        groups.entry(key(query)).or_default().add(query);
+    }
+    groups
+}
+
+// Queries without a layout are grouped under None.
+pub fn spend_by_layout(queries: &[SharpQueryDetails]) -> BTreeMap<Option<Layout>, Spend> {
+    spend_by(queries, |query| query.layout)
+}
+
+// Grouped by the UTC day the query was submitted.
+pub fn spend_by_day(queries: &[SharpQueryDetails]) -> BTreeMap<NaiveDate, Spend> {
+    spend_by(queries, |query| query.created_at.date_naive())
+}
+
+pub fn spend_by_program_hash(queries: &[SharpQueryDetails]) -> BTreeMap<Option<Felt>, Spend> {
+    spend_by(queries, |query| query.program_hash)
+}
+
+impl Context {
+    // Sum of the verification transaction prices reported by a PROOF_VERIFICATION job.
+    pub fn verification_price(&self) -> Decimal {
+        self.verification_transactions()
+            .iter()
+            .map(|(_, transaction)| transaction.price)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        tests::{sharp_query, SHARP_QUERY},
+        SharpQueryResponse,
+    };
+
+    fn query(layout: Layout, price: &str, created_at: &str) -> SharpQueryDetails {
+        let mut query = sharp_query();
+        query.layout = Some(layout);
+        query.price = price.parse().unwrap();
+        query.created_at = created_at.parse().unwrap();
+        query
+    }
+
+    #[test]
+    fn test_spend_aggregates() {
+        let queries = vec![
+            query(Layout::Recursive, "0.85", "2024-11-26T07:55:21.124Z"),
+            query(Layout::Recursive, "0.10", "2024-11-26T23:59:59.999Z"),
+            query(Layout::Dynamic, "1.05", "2024-11-27T00:00:00.000Z"),
+        ];
+        let total = total_spend(&queries);
+        assert_eq!(total.queries, 3);
+        assert_eq!(total.price, Decimal::new(200, 2));
+        assert_eq!(total.gas_used, 3 * 14605);
+
+        let by_layout = spend_by_layout(&queries);
+        assert_eq!(
+            by_layout[&Some(Layout::Recursive)].price,
+            Decimal::new(95, 2)
+        );
+        assert_eq!(by_layout[&Some(Layout::Dynamic)].queries, 1);
+
+        let by_day = spend_by_day(&queries);
+        assert_eq!(by_day.len(), 2);
+        assert_eq!(
+            by_day[&NaiveDate::from_ymd_opt(2024, 11, 27).unwrap()].price,
+            Decimal::new(105, 2)
+        );
+        assert_eq!(spend_by_program_hash(&queries).len(), 1);
+    }
+
+    #[test]
+    fn test_spend_saturates() {
+        let mut query = sharp_query();
+        query.gas_used = u64::MAX;
+        query.price = Decimal::MAX;
+        let total = total_spend(&[query.clone(), query]);
+        assert_eq!(total.gas_used, u64::MAX);
+        assert_eq!(total.price, Decimal::MAX);
+    }
+
+    #[test]
+    fn test_numeric_prices() {
+        let queries = ["0.1", "0.2", "12345678901234.56789"].map(|price| {
+            let query = SHARP_QUERY.replace("\"0.85\"", price);
+            serde_json::from_str::<SharpQueryResponse>(&query)
+                .unwrap()
+                .sharp_query
+        });
+        assert_eq!(queries[0].price, Decimal::new(1, 1));
+        assert_eq!(total_spend(&queries[..2]).price, Decimal::new(3, 1));
+        assert_eq!(
+            queries[2].price,
+            "12345678901234.56789".parse::<Decimal>().unwrap()
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use std::{fmt::Display, str::FromStr};
+
+use integrity::{split_proof, Encode, VerifierConfiguration};
+use swiftness::TransformTo;
+use swiftness_air::layout::recursive_with_poseidon;
+use swiftness_stark::types::StarkProof;
+
+pub use crate::felt::short_string;
+use crate::{
+    error::AtlanticSdkError,
+    felt::Felt,
+    models::{parse_variant, Layout},
+    verify::{from_stark_felt, to_stark_felt},
+};
+
+// Proofs are split with the layout, hasher and Stone version swiftness is compiled for.
+// Integrity also deploys verifiers for keccak_248_lsb, blake2s hashers and stone6, but
+// swiftness can only be built for one of each, so those are not offered here.
+pub const SUPPORTED_LAYOUT: Layout = Layout::RecursiveWithPoseidon;
+pub const SUPPORTED_HASHER: Hasher = Hasher::Keccak160Lsb;
+pub const SUPPORTED_STONE_VERSION: StoneVersion = StoneVersion::Stone5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Hasher {
+    Keccak160Lsb,
+}
+impl Hasher {
+    pub const ALL: [Hasher; 1] = [Hasher::Keccak160Lsb];
+    pub fn iter() -> impl Iterator<Item = Hasher> {
+        Self::ALL.into_iter()
+    }
+}
+impl Display for Hasher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Hasher::Keccak160Lsb => write!(f, "keccak_160_lsb"),
+        }
+    }
+}
+impl FromStr for Hasher {
+    type Err = AtlanticSdkError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_variant("hasher", s, &Self::ALL)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StoneVersion {
+    Stone5,
+}
+impl Display for StoneVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StoneVersion::Stone5 => write!(f, "stone5"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MemoryVerification {
+    Strict,
+    Relaxed,
+    Cairo1,
+}
+impl Display for MemoryVerification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MemoryVerification::Strict => write!(f, "strict"),
+            MemoryVerification::Relaxed => write!(f, "relaxed"),
+            MemoryVerification::Cairo1 => write!(f, "cairo1"),
+        }
+    }
+}
+
+// Selects which Integrity verifier deployment the calldata is meant for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifierSettings {
+    pub layout: Layout,
+    pub hasher: Hasher,
+    pub stone_version: StoneVersion,
+    pub memory_verification: MemoryVerification,
+}
+
+impl VerifierSettings {
+    pub fn to_felts(&self) -> Result<Vec<Felt>, AtlanticSdkError> {
+        Ok(vec![
+            short_string(&self.layout.to_string())?,
+            short_string(&self.hasher.to_string())?,
+            short_string(&self.stone_version.to_string())?,
+            short_string(&self.memory_verification.to_string())?,
+        ])
+    }
+
+    fn configuration(&self) -> Result<VerifierConfiguration, AtlanticSdkError> {
+        if self.layout != SUPPORTED_LAYOUT {
+            return Err(AtlanticSdkError::CustomError(format!(
+                "calldata can only be generated for {} {} proofs hashed with {}",
+                SUPPORTED_LAYOUT, SUPPORTED_STONE_VERSION, SUPPORTED_HASHER
+            )));
+        }
+        let felts = self
+            .to_felts()?
+            .iter()
+            .map(to_stark_felt)
+            .collect::<Vec<_>>();
+        Ok(VerifierConfiguration {
+            layout: felts[0],
+            hasher: felts[1],
+            stone_version: felts[2],
+            memory_verification: felts[3],
+        })
+    }
+}
+
+// Calldata for the split verification flow of the Integrity verifier: `verify_proof_initial`,
+// one `verify_proof_step` per inner FRI layer and `verify_proof_final_and_register_fact`,
+// serialized by the `integrity` crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofCalldata {
+    pub initial: Vec<Felt>,
+    pub steps: Vec<Vec<Felt>>,
+    pub final_: Vec<Felt>,
+}
+
+impl ProofCalldata {
+    // `proof_json` is the proof.json returned by `get_proof`. `job_id` ties the transactions
+    // of one verification together and must not be reused for another proof.
+    pub fn from_json(
+        proof_json: &str,
+        job_id: Felt,
+        settings: &VerifierSettings,
+    ) -> Result<Self, AtlanticSdkError> {
+        let proof = swiftness::parse(proof_json)
+            .map_err(|err| AtlanticSdkError::InvalidProof(err.to_string()))?
+            .transform_to();
+        Self::from_stark_proof(proof, job_id, settings)
+    }
+
+    fn from_stark_proof(
+        proof: StarkProof,
+        job_id: Felt,
+        settings: &VerifierSettings,
+    ) -> Result<Self, AtlanticSdkError> {
+        let config = settings.configuration()?;
+        if proof.public_input.layout != config.layout {
+            return Err(AtlanticSdkError::InvalidProof(format!(
+                "proof layout does not match verifier layout {}",
+                settings.layout
+            )));
+        }
+        let split = split_proof::<recursive_with_poseidon::Layout>(proof)
+            .map_err(|err| AtlanticSdkError::InvalidProof(err.to_string()))?;
+
+        let calls = split.into_calls(to_stark_felt(&job_id), config);
+        Ok(Self {
+            initial: encode(&calls.initial)?,
+            steps: calls
+                .intermediate_steps
+                .iter()
+                .map(encode)
+                .collect::<Result<_, _>>()?,
+            final_: encode(&calls.final_step)?,
+        })
+    }
+}
+
+fn encode(call: &impl Encode) -> Result<Vec<Felt>, AtlanticSdkError> {
+    let mut felts = Vec::new();
+    call.encode(&mut felts)
+        .map_err(|err| AtlanticSdkError::InvalidProof(err.to_string()))?;
+    felts.iter().map(from_stark_felt).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof::tests::PROOF;
+
+    fn settings(layout: Layout) -> VerifierSettings {
+        VerifierSettings {
+            layout,
+            hasher: Hasher::Keccak160Lsb,
+            stone_version: StoneVersion::Stone5,
+            memory_verification: MemoryVerification::Strict,
+        }
+    }
+
+    #[test]
+    fn test_verifier_configuration() {
+        let config = settings(Layout::RecursiveWithPoseidon)
+            .configuration()
+            .unwrap();
+        assert_eq!(
+            config.layout,
+            to_stark_felt(&short_string("recursive_with_poseidon").unwrap())
+        );
+        assert_eq!(
+            config.memory_verification,
+            to_stark_felt(&short_string("strict").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_rejects_unsupported_proofs() {
+        assert!(matches!(
+            "blake2s_248_lsb".parse::<Hasher>(),
+            Err(AtlanticSdkError::InvalidVariant { kind: "hasher", .. })
+        ));
+        assert!(settings(Layout::Recursive).configuration().is_err());
+        // The fixture proof has no annotations to read the commitments from.
+        assert!(matches!(
+            ProofCalldata::from_json(PROOF, Felt::ZERO, &settings(Layout::RecursiveWithPoseidon)),
+            Err(AtlanticSdkError::InvalidProof(_))
+        ));
+    }
+}
@@ -0,0 +1,321 @@
+use std::{fmt::Display, path::Path, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{
+    error::AtlanticSdkError,
+    models::{parse_variant, AtlanticSdk, Network},
+};
+
+pub const API_KEY_VAR: &str = "ATLANTIC_API_KEY";
+pub const PROFILE_VAR: &str = "ATLANTIC_PROFILE";
+pub const CONFIG_VAR: &str = "ATLANTIC_CONFIG";
+pub const BASE_URL_VAR: &str = "ATLANTIC_BASE_URL";
+pub const ARTIFACTS_URL_VAR: &str = "ATLANTIC_ARTIFACTS_URL";
+pub const NETWORK_VAR: &str = "ATLANTIC_NETWORK";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Profile {
+    Mainnet,
+    Testnet,
+    // An Atlantic server and artifact store running on this machine.
+    Local,
+    // Self-hosted deployments; the base and artifacts URLs must be given explicitly.
+    Custom,
+}
+impl Profile {
+    pub const ALL: [Profile; 4] = [
+        Profile::Mainnet,
+        Profile::Testnet,
+        Profile::Local,
+        Profile::Custom,
+    ];
+    pub fn iter() -> impl Iterator<Item = Profile> {
+        Self::ALL.into_iter()
+    }
+}
+impl Display for Profile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Profile::Mainnet => write!(f, "mainnet"),
+            Profile::Testnet => write!(f, "testnet"),
+            Profile::Local => write!(f, "local"),
+            Profile::Custom => write!(f, "custom"),
+        }
+    }
+}
+impl FromStr for Profile {
+    type Err = AtlanticSdkError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_variant("profile", s, &Self::ALL)
+    }
+}
+impl Serialize for Profile {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+impl<'de> Deserialize<'de> for Profile {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Environment {
+    pub base_url: Url,
+    // Where query artifacts (pie.zip, proof.json, ...) are published.
+    pub artifacts_url: Url,
+    // None leaves the network to the server's default.
+    pub network: Option<Network>,
+}
+
+// Configuration file layout: a profile to start from and optional overrides.
+#[derive(Debug, Default, Deserialize)]
+struct EnvironmentConfig {
+    profile: Option<Profile>,
+    base_url: Option<Url>,
+    artifacts_url: Option<Url>,
+    network: Option<Network>,
+}
+
+impl Environment {
+    pub fn mainnet() -> Self {
+        Self {
+            network: Some(Network::Mainnet),
+            ..Self::default()
+        }
+    }
+    pub fn testnet() -> Self {
+        Self {
+            network: Some(Network::Sepolia),
+            ..Self::default()
+        }
+    }
+    // Ports of a locally started Atlantic server and its artifact bucket.
+    pub fn local() -> Self {
+        Self::from_urls(
+            "http://localhost:3000",
+            "http://localhost:9000/atlantic-queries/",
+        )
+    }
+    pub fn custom(base_url: Url, artifacts_url: Url) -> Self {
+        Self {
+            base_url,
+            artifacts_url,
+            network: None,
+        }
+    }
+    // `Profile::Custom` has no URLs of its own, see `custom`.
+    pub fn from_profile(profile: Profile) -> Result<Self, AtlanticSdkError> {
+        match profile {
+            Profile::Mainnet => Ok(Self::mainnet()),
+            Profile::Testnet => Ok(Self::testnet()),
+            Profile::Local => Ok(Self::local()),
+            Profile::Custom => Err(missing_custom_urls()),
+        }
+    }
+
+    // Reads `ATLANTIC_CONFIG` (a config file) or `ATLANTIC_PROFILE`, then applies
+    // `ATLANTIC_BASE_URL`, `ATLANTIC_ARTIFACTS_URL` and `ATLANTIC_NETWORK` on top.
+    pub fn from_env() -> Result<Self, AtlanticSdkError> {
+        let overrides = EnvironmentConfig {
+            profile: env_var(PROFILE_VAR).map(|p| p.parse()).transpose()?,
+            base_url: env_var(BASE_URL_VAR).map(|u| Url::parse(&u)).transpose()?,
+            artifacts_url: env_var(ARTIFACTS_URL_VAR)
+                .map(|u| Url::parse(&u))
+                .transpose()?,
+            network: env_var(NETWORK_VAR).map(|n| n.parse()).transpose()?,
+        };
+        // Variables override the file, except for the profile which the file picks.
+        let config = match std::env::var(CONFIG_VAR) {
+            Ok(path) => {
+                let file = read_config(path.as_ref())?;
+                EnvironmentConfig {
+                    profile: file.profile,
+                    base_url: overrides.base_url.or(file.base_url),
+                    artifacts_url: overrides.artifacts_url.or(file.artifacts_url),
+                    network: overrides.network.or(file.network),
+                }
+            }
+            Err(_) => overrides,
+        };
+        Self::from_config(config)
+    }
+
+    // Accepts `.toml` and `.json` files.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, AtlanticSdkError> {
+        Self::from_config(read_config(path.as_ref())?)
+    }
+
+    fn from_config(config: EnvironmentConfig) -> Result<Self, AtlanticSdkError> {
+        let mut environment = match config.profile {
+            Some(Profile::Custom) => match (&config.base_url, &config.artifacts_url) {
+                (Some(base_url), Some(artifacts_url)) => {
+                    Self::custom(base_url.clone(), artifacts_url.clone())
+                }
+                _ => return Err(missing_custom_urls()),
+            },
+            Some(profile) => Self::from_profile(profile)?,
+            None => Self::default(),
+        };
+        environment.apply(config);
+        Ok(environment)
+    }
+
+    fn from_urls(base_url: &str, artifacts_url: &str) -> Self {
+        Self {
+            base_url: Url::parse(base_url).expect("valid profile url"),
+            artifacts_url: Url::parse(artifacts_url).expect("valid profile url"),
+            network: None,
+        }
+    }
+
+    fn apply(&mut self, config: EnvironmentConfig) {
+        if let Some(base_url) = config.base_url {
+            self.base_url = base_url;
+        }
+        if let Some(artifacts_url) = config.artifacts_url {
+            self.artifacts_url = artifacts_url;
+        }
+        if let Some(network) = config.network {
+            self.network = Some(network);
+        }
+    }
+}
+
+// Production URLs, without choosing a network.
+impl Default for Environment {
+    fn default() -> Self {
+        Self::from_urls(
+            "https://atlantic.api.herodotus.cloud",
+            "https://atlantic-queries.s3.nl-ams.scw.cloud/",
+        )
+    }
+}
+
+impl AtlanticSdk {
+    pub fn from_environment(
+        api_key: String,
+        environment: &Environment,
+    ) -> Result<Self, url::ParseError> {
+        let mut sdk = Self::new(api_key, environment.base_url.clone())?;
+        sdk.network = environment.network;
+        sdk.artifacts_url = environment.artifacts_url.clone();
+        Ok(sdk)
+    }
+
+    // Builds the SDK from `ATLANTIC_API_KEY` and `Environment::from_env`.
+    pub fn from_env() -> Result<Self, AtlanticSdkError> {
+        let api_key = env_var(API_KEY_VAR)
+            .ok_or_else(|| AtlanticSdkError::MissingEnvVar(API_KEY_VAR.to_string()))?;
+        Ok(Self::from_environment(api_key, &Environment::from_env()?)?)
+    }
+}
+
+fn read_config(path: &Path) -> Result<EnvironmentConfig, AtlanticSdkError> {
+    let content = std::fs::read_to_string(path)?;
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => Ok(serde_json::from_str(&content)?),
+        Some("toml") => Ok(toml::from_str(&content)?),
+        _ => Err(AtlanticSdkError::CustomError(format!(
+            "Unsupported config file format: {}",
+            path.display()
+        ))),
+    }
+}
+
+fn missing_custom_urls() -> AtlanticSdkError {
+    AtlanticSdkError::CustomError(format!(
+        "The custom profile needs base_url and artifacts_url ({} and {})",
+        BASE_URL_VAR, ARTIFACTS_URL_VAR
+    ))
+}
+
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let toml_path = dir.path().join("environment.toml");
+        std::fs::write(
+            &toml_path,
+            "profile = \"testnet\"\nbase_url = \"http://atlantic.internal:3000\"\n",
+        )
+        .unwrap();
+        let environment = Environment::from_file(&toml_path).unwrap();
+        assert_eq!(
+            environment.base_url,
+            Url::parse("http://atlantic.internal:3000").unwrap()
+        );
+        assert_eq!(
+            environment.artifacts_url,
+            Environment::testnet().artifacts_url
+        );
+        assert_eq!(environment.network, Some(Network::Sepolia));
+
+        let json_path = dir.path().join("environment.json");
+        std::fs::write(&json_path, r#"{"network": "sepolia"}"#).unwrap();
+        let environment = Environment::from_file(&json_path).unwrap();
+        assert_eq!(environment.base_url, Environment::mainnet().base_url);
+        assert_eq!(environment.network, Some(Network::Sepolia));
+
+        let json_path = dir.path().join("empty.json");
+        std::fs::write(&json_path, "{}").unwrap();
+        assert_eq!(Environment::from_file(&json_path).unwrap().network, None);
+    }
+
+    #[test]
+    fn test_custom_profile() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("environment.toml");
+        std::fs::write(&path, "profile = \"custom\"\n").unwrap();
+        assert!(Environment::from_file(&path).is_err());
+        assert!(Environment::from_profile(Profile::Custom).is_err());
+
+        std::fs::write(
+            &path,
+            "profile = \"custom\"\nbase_url = \"https://atlantic.example.com\"\n\
+             artifacts_url = \"https://artifacts.example.com/\"\n",
+        )
+        .unwrap();
+        let environment = Environment::from_file(&path).unwrap();
+        assert_eq!(
+            environment,
+            Environment::custom(
+                Url::parse("https://atlantic.example.com").unwrap(),
+                Url::parse("https://artifacts.example.com/").unwrap()
+            )
+        );
+        assert_eq!(
+            Environment::from_profile("LOCAL".parse().unwrap()).unwrap(),
+            Environment::local()
+        );
+    }
+
+    #[test]
+    fn test_sdk_from_environment() {
+        let environment = Environment::local();
+        let sdk = AtlanticSdk::from_environment("api_key".to_string(), &environment).unwrap();
+        assert_eq!(
+            sdk.l2.atlantic_query,
+            Url::parse("http://localhost:3000/v1/l2/atlantic-query").unwrap()
+        );
+        assert_eq!(
+            sdk.artifacts_url
+                .join("sharp_queries/query_1/proof.json")
+                .unwrap(),
+            Url::parse("http://localhost:9000/atlantic-queries/sharp_queries/query_1/proof.json")
+                .unwrap()
+        );
+        assert_eq!(sdk.network, None);
+    }
+}
@@ -14,8 +14,96 @@ pub enum AtlanticSdkError {
     UrlParseError(#[from] url::ParseError),
     #[error("Missing program hash or program file")]
     MissingProgramHashOrFile,
+    #[error(transparent)]
+    TomlError(#[from] toml::de::Error),
+    #[error("Missing environment variable {0}")]
+    MissingEnvVar(String),
+    #[error("Program file is neither a Sierra nor a CASM executable")]
+    InvalidProgramFile,
+    #[deprecated(note = "layouts are parsed into `InvalidVariant` errors")]
     #[error("Invalid layout")]
     InvalidLayout,
+    #[error(
+        "Invalid {kind} `{value}`{}, expected one of: {expected}",
+        suggestion.as_ref().map(|s| format!(" (did you mean `{s}`?)")).unwrap_or_default()
+    )]
+    InvalidVariant {
+        kind: &'static str,
+        value: String,
+        suggestion: Option<String>,
+        expected: String,
+    },
+    #[error("Prover {prover} does not support layout {layout}")]
+    UnsupportedLayout { prover: String, layout: String },
+    #[error("Invalid or revoked API key: {0}")]
+    Unauthorized(String),
+    #[error("Insufficient credits: {0}")]
+    InsufficientCredits(String),
+    #[error("Query has no recorded {0} artifact")]
+    MissingArtifact(String),
+    #[error("Query {0} has no program hash to generate a trace with")]
+    MissingProgramHash(String),
+    #[error("Rate limited by Atlantic API")]
+    RateLimited {
+        retry_after: Option<std::time::Duration>,
+    },
+    #[error("Batch aborted after an earlier submission failed")]
+    BatchAborted,
+    #[error("Failed to poll sharpQueryId {id}: {source}")]
+    WatchFailed {
+        id: String,
+        source: Box<AtlanticSdkError>,
+        // No further polls are made for this query.
+        gave_up: bool,
+    },
+    #[error("Corrupt ledger: {0}")]
+    CorruptLedger(String),
+    #[error("Invalid webhook signature")]
+    InvalidWebhookSignature,
+    #[error("Invalid proof: {0}")]
+    InvalidProof(String),
+    #[error("Proof verification failed: {0}")]
+    VerificationFailed(String),
+    #[error("Invalid field element: {0}")]
+    InvalidFelt(String),
+    #[error("Invalid program input: {0}")]
+    InvalidProgramInput(String),
+    #[error("Invalid Cairo PIE: {0}")]
+    InvalidPie(String),
+    #[error("Crate feature `{0}` is not enabled")]
+    FeatureNotEnabled(&'static str),
+    #[error("Local trace generation failed: {0}")]
+    TraceGenerationFailed(String),
+    #[error("Timed out waiting for query {0}")]
+    Timeout(String),
     #[error("{0}")]
     CustomError(String),
 }
+
+impl AtlanticSdkError {
+    pub(crate) async fn from_response(response: reqwest::Response) -> Self {
+        let status = response.status();
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse().ok())
+                .map(std::time::Duration::from_secs);
+            return AtlanticSdkError::RateLimited { retry_after };
+        }
+        let response = match response.text().await {
+            Ok(response) => response,
+            Err(err) => return err.into(),
+        };
+        match status {
+            reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => {
+                AtlanticSdkError::Unauthorized(response)
+            }
+            reqwest::StatusCode::PAYMENT_REQUIRED => {
+                AtlanticSdkError::InsufficientCredits(response)
+            }
+            _ => AtlanticSdkError::CustomError(response),
+        }
+    }
+}
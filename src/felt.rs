@@ -0,0 +1,255 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::AtlanticSdkError;
+
+// Little-endian 64-bit limbs of the Stark prime 2^251 + 17 * 2^192 + 1.
+const PRIME: [u64; 4] = [1, 0, 0, 0x0800_0000_0000_0011];
+
+// Element of the Cairo field, parsed from `0x`-prefixed hex or decimal. Compared by value and
+// displayed as lowercase hex without leading zeros, so hashes from different sources match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Felt([u64; 4]);
+
+// Limbs are stored least significant first, so compare from the other end.
+impl Ord for Felt {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl PartialOrd for Felt {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Felt {
+    pub const ZERO: Felt = Felt([0; 4]);
+
+    pub fn from_bytes_le(bytes: &[u8; 32]) -> Result<Self, AtlanticSdkError> {
+        let mut limbs = [0u64; 4];
+        for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks_exact(8)) {
+            *limb = u64::from_le_bytes(chunk.try_into().expect("chunk of 8 bytes"));
+        }
+        Self::from_limbs(limbs)
+            .ok_or_else(|| AtlanticSdkError::InvalidFelt(format!("0x{}", hex_be(bytes))))
+    }
+
+    pub fn to_bytes_le(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (chunk, limb) in bytes.chunks_exact_mut(8).zip(self.0) {
+            chunk.copy_from_slice(&limb.to_le_bytes());
+        }
+        bytes
+    }
+
+    pub fn to_decimal_string(&self) -> String {
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+        let mut limbs = self.0;
+        let mut chunks = Vec::new();
+        while limbs != [0; 4] {
+            let mut remainder = 0u128;
+            for limb in limbs.iter_mut().rev() {
+                let value = (remainder << 64) | u128::from(*limb);
+                *limb = (value / u128::from(CHUNK)) as u64;
+                remainder = value % u128::from(CHUNK);
+            }
+            chunks.push(remainder as u64);
+        }
+        match chunks.split_last() {
+            None => "0".to_string(),
+            Some((last, rest)) => rest.iter().rev().fold(last.to_string(), |mut s, chunk| {
+                s.push_str(&format!("{:019}", chunk));
+                s
+            }),
+        }
+    }
+
+    // The value as u64, when it fits.
+    pub fn to_u64(&self) -> Option<u64> {
+        (self.0[1..] == [0; 3]).then_some(self.0[0])
+    }
+
+    fn from_limbs(limbs: [u64; 4]) -> Option<Self> {
+        limbs
+            .iter()
+            .rev()
+            .lt(PRIME.iter().rev())
+            .then_some(Felt(limbs))
+    }
+}
+
+impl From<u64> for Felt {
+    fn from(value: u64) -> Self {
+        Felt([value, 0, 0, 0])
+    }
+}
+
+impl FromStr for Felt {
+    type Err = AtlanticSdkError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || AtlanticSdkError::InvalidFelt(s.to_string());
+        let (digits, radix) = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            Some(hex) => (hex, 16),
+            None => (s, 10),
+        };
+        if digits.is_empty() {
+            return Err(invalid());
+        }
+        let mut limbs = [0u64; 4];
+        for c in digits.chars() {
+            let digit = c.to_digit(radix).ok_or_else(invalid)?;
+            let mut carry = u128::from(digit);
+            for limb in limbs.iter_mut() {
+                let value = u128::from(*limb) * u128::from(radix) + carry;
+                *limb = value as u64;
+                carry = value >> 64;
+            }
+            if carry != 0 {
+                return Err(invalid());
+            }
+        }
+        Self::from_limbs(limbs).ok_or_else(invalid)
+    }
+}
+
+impl Display for Felt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut bytes = self.to_bytes_le();
+        bytes.reverse();
+        let hex = hex_be(&bytes);
+        let hex = hex.trim_start_matches('0');
+        write!(f, "0x{}", if hex.is_empty() { "0" } else { hex })
+    }
+}
+
+impl Serialize for Felt {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Felt {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FeltVisitor;
+        impl<'de> de::Visitor<'de> for FeltVisitor {
+            type Value = Felt;
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(
+                    f,
+                    "a field element as a hex or decimal string, or an integer up to u64"
+                )
+            }
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Felt, E> {
+                v.parse().map_err(E::custom)
+            }
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Felt, E> {
+                Ok(Felt::from(v))
+            }
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Felt, E> {
+                u64::try_from(v)
+                    .map(Felt::from)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Signed(v), &self))
+            }
+            fn visit_u128<E: de::Error>(self, v: u128) -> Result<Felt, E> {
+                v.to_string().parse().map_err(E::custom)
+            }
+            // With serde_json's `arbitrary_precision`, JSON numbers above u64 arrive as a map
+            // private to serde_json and are rejected; larger values must be strings.
+        }
+        deserializer.deserialize_any(FeltVisitor)
+    }
+}
+
+fn hex_be(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// Cairo short string: ASCII bytes read as a big-endian integer, at most 31 characters.
+pub fn short_string(s: &str) -> Result<Felt, AtlanticSdkError> {
+    if s.len() > 31 || !s.is_ascii() {
+        return Err(AtlanticSdkError::CustomError(format!(
+            "{} is not a valid short string",
+            s
+        )));
+    }
+    let hex: String = s.bytes().map(|b| format!("{:02x}", b)).collect();
+    format!("0x{}", if hex.is_empty() { "0" } else { &hex }).parse()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_felt() {
+        let prime_minus_one =
+            "3618502788666131213697322783095070105623107215331596699973092056135872020480";
+        let felt: Felt = prime_minus_one.parse().unwrap();
+        assert_eq!(felt.to_decimal_string(), prime_minus_one);
+        assert_eq!(
+            felt.to_string(),
+            "0x800000000000011000000000000000000000000000000000000000000000000"
+        );
+        assert_eq!(Felt::from_bytes_le(&felt.to_bytes_le()).unwrap(), felt);
+        assert_eq!("0x2a".parse::<Felt>().unwrap(), Felt::from(42));
+        assert_eq!(Felt::ZERO.to_decimal_string(), "0");
+        assert!(
+            "3618502788666131213697322783095070105623107215331596699973092056135872020481"
+                .parse::<Felt>()
+                .is_err()
+        );
+        assert!("0xg".parse::<Felt>().is_err());
+    }
+
+    #[test]
+    fn test_felt_serde() {
+        let padded: Felt = serde_json::from_str(r#""0x0000002A""#).unwrap();
+        let decimal: Felt = serde_json::from_str(r#""42""#).unwrap();
+        let number: Felt = serde_json::from_str("42").unwrap();
+        assert_eq!(padded, decimal);
+        assert_eq!(padded, number);
+        assert_eq!(serde_json::to_string(&padded).unwrap(), r#""0x2a""#);
+
+        let big = "340282366920938463463374607431768211456";
+        let felt: Felt = serde_json::from_str(&format!("\"{}\"", big)).unwrap();
+        assert_eq!(felt.to_decimal_string(), big);
+        assert!(serde_json::from_str::<Felt>(big).is_err());
+        let max_u128 = Felt::deserialize(
+            de::IntoDeserializer::<de::value::Error>::into_deserializer(u128::MAX),
+        )
+        .unwrap();
+        assert_eq!(max_u128.to_decimal_string(), u128::MAX.to_string());
+        assert!(serde_json::from_str::<Felt>("-1").is_err());
+        assert!(serde_json::from_str::<Felt>("1.5").is_err());
+    }
+
+    #[test]
+    fn test_felt_order() {
+        let mut felts = vec![
+            Felt::from(u64::MAX),
+            "0x10000000000000000".parse::<Felt>().unwrap(),
+            Felt::from(1),
+        ];
+        felts.sort();
+        assert_eq!(
+            felts,
+            vec![
+                Felt::from(1),
+                Felt::from(u64::MAX),
+                "0x10000000000000000".parse().unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_short_string() {
+        assert_eq!(
+            short_string("recursive").unwrap().to_string(),
+            "0x726563757273697665"
+        );
+        assert!(short_string(&"a".repeat(32)).is_err());
+    }
+}
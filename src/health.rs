@@ -0,0 +1,195 @@
+use std::time::{Duration, Instant};
+
+use reqwest::StatusCode;
+use tracing::{info, warn};
+
+use crate::{models::AtlanticSdk, rate_limit::EndpointKind};
+
+// Headers checked, in order, for the version of the server behind the API.
+const VERSION_HEADERS: [&str; 3] = ["x-api-version", "x-app-version", "server"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthStatus {
+    Valid,
+    Invalid,
+    // The authenticated call could not tell, e.g. the API is unreachable.
+    Unknown,
+}
+
+#[derive(Debug, Clone)]
+pub struct HealthStatus {
+    pub reachable: bool,
+    pub status: Option<StatusCode>,
+    pub latency: Duration,
+    pub server_version: Option<String>,
+    pub auth: AuthStatus,
+}
+
+impl HealthStatus {
+    pub fn is_alive(&self) -> bool {
+        self.status.is_some_and(|status| status.is_success())
+    }
+    pub fn is_healthy(&self) -> bool {
+        self.is_alive() && self.auth == AuthStatus::Valid
+    }
+}
+
+impl AtlanticSdk {
+    // Connection failures (DNS, refused, timeouts) are reported as unreachable, not as errors.
+    pub async fn health(&self) -> HealthStatus {
+        info!("Checking Atlantic API health");
+        let start = Instant::now();
+        let response = self
+            .client
+            .get(self.health_check.is_alive.clone())
+            .send()
+            .await;
+        let latency = start.elapsed();
+        let response = match response {
+            Ok(response) => response,
+            Err(err) => {
+                warn!("Atlantic API is unreachable: {}", err);
+                return HealthStatus {
+                    reachable: false,
+                    status: None,
+                    latency,
+                    server_version: None,
+                    auth: AuthStatus::Unknown,
+                };
+            }
+        };
+        let server_version = VERSION_HEADERS.iter().find_map(|header| {
+            response
+                .headers()
+                .get(*header)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        });
+        let status = response.status();
+        // A failing server would fail the auth probe too; don't report that as an auth problem.
+        let auth = if status.is_server_error() {
+            warn!(
+                "Atlantic API is unhealthy ({}), skipping auth check",
+                status
+            );
+            AuthStatus::Unknown
+        } else {
+            self.check_auth().await
+        };
+        HealthStatus {
+            reachable: true,
+            status: Some(status),
+            latency,
+            server_version,
+            auth,
+        }
+    }
+
+    async fn check_auth(&self) -> AuthStatus {
+        self.throttle(EndpointKind::Read).await;
+        let response = self
+            .client
+            .get(self.atlantic_queries.get_queries.clone())
+            .query(&[("apiKey", self.api_key.as_str()), ("limit", "1")])
+            .send()
+            .await;
+        match response.map(|response| response.status()) {
+            Ok(status) if status.is_success() => AuthStatus::Valid,
+            Ok(StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN) => AuthStatus::Invalid,
+            _ => AuthStatus::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use axum::{http::StatusCode, routing::get, Router};
+
+    use super::*;
+    use crate::mock_server;
+
+    fn router(queries_status: StatusCode, queries_hits: Arc<AtomicUsize>) -> Router {
+        router_with_alive(StatusCode::OK, queries_status, queries_hits)
+    }
+
+    fn router_with_alive(
+        alive_status: StatusCode,
+        queries_status: StatusCode,
+        queries_hits: Arc<AtomicUsize>,
+    ) -> Router {
+        Router::new()
+            .route(
+                "/v1/is-alive",
+                get(move || async move { (alive_status, [("x-api-version", "1.2.3")], "OK") }),
+            )
+            .route(
+                "/v1/atlantic-queries",
+                get(move || async move {
+                    queries_hits.fetch_add(1, Ordering::SeqCst);
+                    (queries_status, "{\"sharpQueries\": [], \"total\": 0}")
+                }),
+            )
+    }
+
+    #[tokio::test]
+    async fn test_health_valid_auth() {
+        let sdk = mock_server::sdk(router(StatusCode::OK, Arc::default())).await;
+        let health = sdk.health().await;
+        assert!(health.reachable);
+        assert_eq!(health.status, Some(StatusCode::OK));
+        assert_eq!(health.server_version.as_deref(), Some("1.2.3"));
+        assert_eq!(health.auth, AuthStatus::Valid);
+        assert!(health.is_healthy());
+    }
+
+    #[tokio::test]
+    async fn test_health_invalid_auth() {
+        let sdk = mock_server::sdk(router(StatusCode::UNAUTHORIZED, Arc::default())).await;
+        let health = sdk.health().await;
+        assert!(health.is_alive());
+        assert_eq!(health.auth, AuthStatus::Invalid);
+        assert!(!health.is_healthy());
+
+        let sdk = mock_server::sdk(router(StatusCode::BAD_GATEWAY, Arc::default())).await;
+        assert_eq!(sdk.health().await.auth, AuthStatus::Unknown);
+    }
+
+    #[tokio::test]
+    async fn test_health_unreachable() {
+        let sdk = AtlanticSdk::new("api_key".to_string(), mock_server::closed_url().await).unwrap();
+        let health = sdk.health().await;
+        assert!(!health.reachable);
+        assert_eq!(health.status, None);
+        assert_eq!(health.auth, AuthStatus::Unknown);
+        assert!(!health.is_alive());
+    }
+
+    #[tokio::test]
+    async fn test_health_server_error_skips_auth() {
+        let queries_hits = Arc::new(AtomicUsize::new(0));
+        let sdk = mock_server::sdk(router_with_alive(
+            StatusCode::SERVICE_UNAVAILABLE,
+            StatusCode::UNAUTHORIZED,
+            queries_hits.clone(),
+        ))
+        .await;
+        let health = sdk.health().await;
+        assert!(health.reachable);
+        assert_eq!(health.status, Some(StatusCode::SERVICE_UNAVAILABLE));
+        assert_eq!(health.auth, AuthStatus::Unknown);
+        assert_eq!(queries_hits.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_is_alive_skips_auth() {
+        let queries_hits = Arc::new(AtomicUsize::new(0));
+        let sdk = mock_server::sdk(router(StatusCode::OK, queries_hits.clone())).await;
+        assert!(sdk.get_is_alive().await.unwrap());
+        assert_eq!(queries_hits.load(Ordering::SeqCst), 0);
+    }
+}
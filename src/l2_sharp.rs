@@ -1,51 +1,33 @@
 use crate::{
+    atlantic_query::SubmitForm,
     error::AtlanticSdkError,
-    models::{AtlanticSdk, FactHashResponse, Layout, ProverVersion, QueryResponse},
+    models::{
+        AtlanticSdk, CairoVersion, Chain, FactHashResponse, Layout, ProverVersion, QueryResponse,
+    },
+    proof_upload::ProofUpload,
 };
-use reqwest::multipart;
 
 impl AtlanticSdk {
     pub async fn l2_atlantic_query(
         &self,
         program_file: Vec<u8>,
         input_file: Vec<u8>,
+        cairo_version: CairoVersion,
         prover: ProverVersion,
         mock_fact_hash: bool,
         external_id: &str,
     ) -> Result<QueryResponse, AtlanticSdkError> {
-        let form = multipart::Form::new()
-            .part(
-                "programFile",
-                multipart::Part::bytes(program_file)
-                    .file_name("program.json")
-                    .mime_str("application/json")?,
-            )
-            .part(
-                "inputFile",
-                multipart::Part::bytes(input_file)
-                    .file_name("input.json")
-                    .mime_str("application/json")?,
-            )
-            .text("cairoVersion", 0.to_string())
-            .text("prover", prover.to_string())
-            .text("mockFactHash", mock_fact_hash.to_string())
-            .text("externalId", external_id.to_string());
-        let client = reqwest::Client::new();
-        let url = format!("{}?apiKey={}", self.l2.atlantic_query, self.api_key);
-        let response = client.post(&url).multipart(form).send().await?;
-
-        let status = response.status();
-
-        match status {
-            reqwest::StatusCode::CREATED => {
-                let response = response.json::<QueryResponse>().await?;
-                Ok(response)
-            }
-            _ => {
-                let response = response.text().await?;
-                Err(AtlanticSdkError::CustomError(response))
-            }
-        }
+        self.atlantic_query(
+            Chain::L2,
+            None,
+            program_file,
+            input_file,
+            cairo_version,
+            prover,
+            mock_fact_hash,
+            external_id,
+        )
+        .await
     }
 
     //Works
@@ -57,39 +39,19 @@ impl AtlanticSdk {
         mock_fact_hash: bool,
         external_id: &str,
     ) -> Result<QueryResponse, AtlanticSdkError> {
-        let form = multipart::Form::new()
-            .part(
-                "pieFile",
-                multipart::Part::bytes(pie_file)
-                    .file_name("pie.zip")
-                    .mime_str("application/zip")?,
-            )
-            .text("layout", layout.to_string())
-            .text("prover", prover.to_string())
-            .text("mockFactHash", mock_fact_hash.to_string())
-            .text("externalId", external_id.to_string());
-
-        let client = reqwest::Client::new();
-        let url = format!(
-            "{}?apiKey={}",
-            self.l2.from_proof_generation_to_proof_verification, self.api_key
-        );
-        let response = client.post(&url).multipart(form).send().await?;
-
-        let status = response.status();
-
-        match status {
-            reqwest::StatusCode::CREATED => {
-                let response = response.json::<QueryResponse>().await?;
-                Ok(response)
-            }
-            _ => {
-                let response = response.text().await?;
-                Err(AtlanticSdkError::CustomError(response))
-            }
-        }
+        self.proof_generation_verification(
+            Chain::L2,
+            pie_file,
+            layout,
+            prover,
+            mock_fact_hash,
+            external_id,
+        )
+        .await
     }
 
+    // Uploads `proof_file` as `proof.zip` without inspecting it. Use
+    // `l2_proof_verification_upload` to validate the proof first.
     pub async fn l2_proof_verification(
         &self,
         proof_file: Vec<u8>,
@@ -97,64 +59,35 @@ impl AtlanticSdk {
         stone_version: ProverVersion,
         external_id: &str,
     ) -> Result<QueryResponse, AtlanticSdkError> {
-        let form = multipart::Form::new()
-            .part(
-                "proofFile",
-                multipart::Part::bytes(proof_file)
-                    .file_name("proof.zip")
-                    .mime_str("application/zip")?,
-            )
+        self.l2_proof_verification_upload(
+            ProofUpload::Zip(proof_file),
+            mock_fact_hash,
+            stone_version,
+            external_id,
+        )
+        .await
+    }
+
+    pub async fn l2_proof_verification_upload(
+        &self,
+        proof: ProofUpload,
+        mock_fact_hash: bool,
+        stone_version: ProverVersion,
+        external_id: &str,
+    ) -> Result<QueryResponse, AtlanticSdkError> {
+        let form = SubmitForm::new()
+            .part("proofFile", proof.into_part())
             .text("mockFactHash", mock_fact_hash.to_string())
             .text("stoneVersion", stone_version.to_string())
+            .network(self.network)
             .text("externalId", external_id.to_string());
-
-        let client = reqwest::Client::new();
-        let url = format!("{}?apiKey={}", self.l2.proof_verification, self.api_key);
-        let response = client.post(&url).multipart(form).send().await?;
-
-        let status = response.status();
-
-        match status {
-            reqwest::StatusCode::CREATED => {
-                let response = response.json::<QueryResponse>().await?;
-                Ok(response)
-            }
-            _ => {
-                let response = response.text().await?;
-                Err(AtlanticSdkError::CustomError(response))
-            }
-        }
+        self.submit_query(&self.l2.proof_verification, form).await
     }
+
     pub async fn l2_fact_hash_calculation(
         &self,
         pie_file: Vec<u8>,
     ) -> Result<FactHashResponse, AtlanticSdkError> {
-        let form = multipart::Form::new().part(
-            "pieFile",
-            multipart::Part::bytes(pie_file)
-                .file_name("pie.zip")
-                .mime_str("application/zip")?,
-        );
-
-        let client = reqwest::Client::new();
-        let response = client
-            .post(self.l2.fact_hash_calculation.clone())
-            .query(&[("apiKey", &self.api_key)]) // Add API key as a query parameter
-            .multipart(form)
-            .send()
-            .await?;
-
-        let status = response.status();
-
-        match status {
-            reqwest::StatusCode::CREATED => {
-                let response = response.json::<FactHashResponse>().await?;
-                Ok(response)
-            }
-            _ => {
-                let response = response.text().await?;
-                Err(AtlanticSdkError::CustomError(response))
-            }
-        }
+        self.fact_hash_calculation(Chain::L2, pie_file).await
     }
 }
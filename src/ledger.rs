@@ -0,0 +1,464 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use chrono::{
+    serde::{ts_milliseconds, ts_milliseconds_option},
+    DateTime, Utc,
+};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tokio::{io::AsyncWriteExt, sync::Mutex};
+use tracing::warn;
+
+use crate::{
+    error::AtlanticSdkError,
+    models::{AtlanticSdk, QueryStatus, SharpQueryDetails},
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LedgerEntry {
+    pub atlantic_query_id: String,
+    pub external_id: String,
+    // Path of the endpoint the query was submitted to, e.g. `/v1/proof-generation`.
+    pub endpoint: String,
+    // Text fields of the submission; uploaded files are not recorded.
+    pub params: BTreeMap<String, String>,
+    // Stored as unix milliseconds.
+    #[serde(with = "ts_milliseconds")]
+    pub submitted_at: DateTime<Utc>,
+    #[serde(with = "ts_milliseconds")]
+    pub updated_at: DateTime<Utc>,
+    pub status: Option<QueryStatus>,
+    pub step: Option<String>,
+    pub price: Option<Decimal>,
+    pub gas_used: Option<u64>,
+    pub credits_used: Option<u64>,
+    pub trace_credits_used: Option<u64>,
+    // Set once the final result was delivered, by `wait_for_query` or a `resume_pending` handler.
+    #[serde(default)]
+    pub handled: bool,
+    // Set by `Ledger::claim` while a `resume_pending` handler runs. Claims left behind by a
+    // previous process are released by `Ledger::open`.
+    #[serde(default, with = "ts_milliseconds_option")]
+    pub claimed_at: Option<DateTime<Utc>>,
+    // Id of the query this one was resubmitted from, see `AtlanticSdk::resubmit`.
+    #[serde(default)]
+    pub resubmitted_from: Option<String>,
+}
+
+impl LedgerEntry {
+    pub(crate) fn submitted(
+        atlantic_query_id: &str,
+        endpoint: &str,
+        params: BTreeMap<String, String>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            atlantic_query_id: atlantic_query_id.to_string(),
+            external_id: params.get("externalId").cloned().unwrap_or_default(),
+            endpoint: endpoint.to_string(),
+            params,
+            submitted_at: now,
+            updated_at: now,
+            status: None,
+            step: None,
+            price: None,
+            gas_used: None,
+            credits_used: None,
+            trace_credits_used: None,
+            handled: false,
+            claimed_at: None,
+            resubmitted_from: None,
+        }
+    }
+
+    pub fn is_pending(&self) -> bool {
+        !self.status.is_some_and(|status| status.is_terminal())
+    }
+
+    // Returns false when the details carry nothing new for this entry.
+    fn apply(&mut self, query: &SharpQueryDetails) -> bool {
+        if self.status == Some(query.status)
+            && self.step == query.step
+            && self.price == Some(query.price)
+            && self.gas_used == Some(query.gas_used)
+            && self.credits_used == Some(query.credits_used)
+            && self.trace_credits_used == Some(query.trace_credits_used)
+        {
+            return false;
+        }
+        self.status = Some(query.status);
+        self.step = query.step.clone();
+        self.price = Some(query.price);
+        self.gas_used = Some(query.gas_used);
+        self.credits_used = Some(query.credits_used);
+        self.trace_credits_used = Some(query.trace_credits_used);
+        self.updated_at = Utc::now();
+        true
+    }
+}
+
+// Append-only JSONL record of submitted queries. Every change appends the full entry,
+// the last line for a query id wins when the file is loaded. Opening the ledger rewrites
+// the file with one line per query when it holds superseded lines, a truncated last line or
+// stale claims; a malformed line anywhere else fails `open` and leaves the file untouched.
+// A ledger file is meant to be used by one process at a time.
+#[derive(Debug, Clone)]
+pub struct Ledger {
+    path: PathBuf,
+    entries: Arc<Mutex<HashMap<String, LedgerEntry>>>,
+}
+
+impl Ledger {
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self, AtlanticSdkError> {
+        let path = path.as_ref().to_path_buf();
+        let mut entries = HashMap::new();
+        let mut lines = 0;
+        match tokio::fs::read_to_string(&path).await {
+            Ok(content) => {
+                let content: Vec<&str> = content.lines().collect();
+                let last_line = content.iter().rposition(|line| !line.trim().is_empty());
+                for (line_number, line) in content.into_iter().enumerate() {
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    lines += 1;
+                    match serde_json::from_str::<LedgerEntry>(line) {
+                        Ok(entry) => {
+                            entries.insert(entry.atlantic_query_id.clone(), entry);
+                        }
+                        // A crash mid-write can leave a truncated last line.
+                        Err(err) if Some(line_number) == last_line => warn!(
+                            "Skipping truncated last ledger line {} in {}: {}",
+                            line_number + 1,
+                            path.display(),
+                            err
+                        ),
+                        Err(err) => {
+                            return Err(AtlanticSdkError::CorruptLedger(format!(
+                                "line {} of {}: {}",
+                                line_number + 1,
+                                path.display(),
+                                err
+                            )))
+                        }
+                    }
+                }
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+        // Claims only live as long as the process whose handler made them; one that died
+        // mid-handler gets its queries delivered again.
+        let mut stale_claims = false;
+        for entry in entries.values_mut() {
+            if entry.claimed_at.take().is_some() {
+                warn!(
+                    "Releasing stale claim on sharpQueryId {}",
+                    entry.atlantic_query_id
+                );
+                stale_claims = true;
+            }
+        }
+        let ledger = Self {
+            path,
+            entries: Arc::new(Mutex::new(entries)),
+        };
+        if stale_claims || lines > ledger.entries.lock().await.len() {
+            ledger.compact().await?;
+        }
+        Ok(ledger)
+    }
+
+    // Rewrites the file with the current entry of every query. The new file is written next
+    // to the old one and renamed over it, so a crash leaves one of the two intact.
+    pub async fn compact(&self) -> Result<(), AtlanticSdkError> {
+        let entries = self.entries.lock().await;
+        let mut sorted: Vec<_> = entries.values().collect();
+        sorted.sort_by_key(|entry| entry.submitted_at);
+        let mut content = Vec::new();
+        for entry in sorted {
+            serde_json::to_writer(&mut content, entry)?;
+            content.push(b'\n');
+        }
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+        let mut file = tokio::fs::File::create(&tmp_path).await?;
+        file.write_all(&content).await?;
+        file.sync_all().await?;
+        tokio::fs::rename(&tmp_path, &self.path).await?;
+        Ok(())
+    }
+
+    pub async fn record(&self, entry: LedgerEntry) -> Result<(), AtlanticSdkError> {
+        let mut entries = self.entries.lock().await;
+        self.append(&entry).await?;
+        entries.insert(entry.atlantic_query_id.clone(), entry);
+        Ok(())
+    }
+
+    // Updates status and cost of a known query. Queries not in the ledger are ignored.
+    pub async fn update(&self, query: &SharpQueryDetails) -> Result<(), AtlanticSdkError> {
+        let mut entries = self.entries.lock().await;
+        let Some(entry) = entries.get(&query.id) else {
+            return Ok(());
+        };
+        let mut entry = entry.clone();
+        if entry.apply(query) {
+            self.append(&entry).await?;
+            entries.insert(entry.atlantic_query_id.clone(), entry);
+        }
+        Ok(())
+    }
+
+    pub async fn mark_handled(&self, atlantic_query_id: &str) -> Result<(), AtlanticSdkError> {
+        self.change(atlantic_query_id, |entry| {
+            entry.handled = true;
+            true
+        })
+        .await?;
+        Ok(())
+    }
+
+    // Marks an unhandled, unclaimed query as about to be handled and persists that before
+    // returning true. Returns false when the query is unknown, handled or already claimed.
+    pub async fn claim(&self, atlantic_query_id: &str) -> Result<bool, AtlanticSdkError> {
+        self.change(atlantic_query_id, |entry| {
+            if entry.handled || entry.claimed_at.is_some() {
+                return false;
+            }
+            entry.claimed_at = Some(Utc::now());
+            true
+        })
+        .await
+    }
+
+    // Makes a claimed query eligible for `resume_pending` again.
+    pub async fn release(&self, atlantic_query_id: &str) -> Result<(), AtlanticSdkError> {
+        self.change(atlantic_query_id, |entry| entry.claimed_at.take().is_some())
+            .await?;
+        Ok(())
+    }
+
+    pub async fn mark_resubmitted_from(
+        &self,
+        atlantic_query_id: &str,
+        original_query_id: &str,
+    ) -> Result<(), AtlanticSdkError> {
+        self.change(atlantic_query_id, |entry| {
+            entry.resubmitted_from = Some(original_query_id.to_string());
+            true
+        })
+        .await?;
+        Ok(())
+    }
+
+    // Applies `change` to a known query and appends the entry when it reports a change.
+    async fn change(
+        &self,
+        atlantic_query_id: &str,
+        change: impl FnOnce(&mut LedgerEntry) -> bool,
+    ) -> Result<bool, AtlanticSdkError> {
+        let mut entries = self.entries.lock().await;
+        let Some(entry) = entries.get(atlantic_query_id) else {
+            return Ok(false);
+        };
+        let mut entry = entry.clone();
+        if !change(&mut entry) {
+            return Ok(false);
+        }
+        entry.updated_at = Utc::now();
+        self.append(&entry).await?;
+        entries.insert(entry.atlantic_query_id.clone(), entry);
+        Ok(true)
+    }
+
+    pub async fn get(&self, atlantic_query_id: &str) -> Option<LedgerEntry> {
+        self.entries.lock().await.get(atlantic_query_id).cloned()
+    }
+
+    pub async fn find_by_external_id(&self, external_id: &str) -> Vec<LedgerEntry> {
+        self.filter(|entry| entry.external_id == external_id).await
+    }
+
+    pub async fn find_resubmissions(&self, original_query_id: &str) -> Vec<LedgerEntry> {
+        self.filter(|entry| entry.resubmitted_from.as_deref() == Some(original_query_id))
+            .await
+    }
+
+    pub async fn pending(&self) -> Vec<LedgerEntry> {
+        self.filter(LedgerEntry::is_pending).await
+    }
+
+    // Queries whose final result was never delivered nor claimed, terminal or not.
+    pub async fn unhandled(&self) -> Vec<LedgerEntry> {
+        self.filter(|entry| !entry.handled && entry.claimed_at.is_none())
+            .await
+    }
+
+    // Queries whose `resume_pending` handler is still running in this process.
+    pub async fn claimed(&self) -> Vec<LedgerEntry> {
+        self.filter(|entry| !entry.handled && entry.claimed_at.is_some())
+            .await
+    }
+
+    pub async fn entries(&self) -> Vec<LedgerEntry> {
+        self.filter(|_| true).await
+    }
+
+    async fn filter(&self, predicate: impl Fn(&LedgerEntry) -> bool) -> Vec<LedgerEntry> {
+        let mut entries: Vec<_> = self
+            .entries
+            .lock()
+            .await
+            .values()
+            .filter(|entry| predicate(entry))
+            .cloned()
+            .collect();
+        entries.sort_by_key(|entry| entry.submitted_at);
+        entries
+    }
+
+    async fn append(&self, entry: &LedgerEntry) -> Result<(), AtlanticSdkError> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(&line).await?;
+        file.sync_data().await?;
+        Ok(())
+    }
+}
+
+impl AtlanticSdk {
+    pub fn with_ledger(mut self, ledger: Ledger) -> Self {
+        self.ledger = Some(ledger);
+        self
+    }
+
+    pub(crate) async fn record_query_details(&self, query: &SharpQueryDetails) {
+        if let Some(ledger) = &self.ledger {
+            if let Err(err) = ledger.update(query).await {
+                warn!(
+                    "Failed to update sharpQueryId {} in ledger: {}",
+                    query.id, err
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::models::tests::sharp_query;
+
+    #[tokio::test]
+    async fn test_ledger_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger.jsonl");
+        let ledger = Ledger::open(&path).await.unwrap();
+        let params = BTreeMap::from([("externalId".to_string(), "block-42".to_string())]);
+        ledger
+            .record(LedgerEntry::submitted(
+                "01JDKQF9VY2NDBFZAFNFXZC17Z",
+                "/v1/proof-generation",
+                params,
+            ))
+            .await
+            .unwrap();
+        ledger
+            .record(LedgerEntry::submitted(
+                "01JDKQF21NWMENSXK96E3N7CE8",
+                "/v1/proof-generation",
+                BTreeMap::new(),
+            ))
+            .await
+            .unwrap();
+        let mut query = sharp_query();
+        query.id = "01JDKQF21NWMENSXK96E3N7CE8".to_string();
+        query.price = Decimal::new(91, 2);
+        ledger.update(&query).await.unwrap();
+
+        let reloaded = Ledger::open(&path).await.unwrap();
+        let pending = reloaded.pending().await;
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].external_id, "block-42");
+        let done = reloaded.get("01JDKQF21NWMENSXK96E3N7CE8").await.unwrap();
+        assert_eq!(done.status, Some(QueryStatus::Done));
+        assert_eq!(done.price, Some(Decimal::new(91, 2)));
+        assert_eq!(reloaded.unhandled().await.len(), 2);
+        reloaded
+            .mark_handled("01JDKQF21NWMENSXK96E3N7CE8")
+            .await
+            .unwrap();
+        let reloaded = Ledger::open(&path).await.unwrap();
+        assert_eq!(reloaded.unhandled().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_ledger_compaction() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger.jsonl");
+        let line_count = || std::fs::read_to_string(&path).unwrap().lines().count();
+        let ledger = Ledger::open(&path).await.unwrap();
+        ledger
+            .record(LedgerEntry::submitted(
+                "01JDKQF9VY2NDBFZAFNFXZC17Z",
+                "/v1/proof-generation",
+                BTreeMap::new(),
+            ))
+            .await
+            .unwrap();
+        let mut query = sharp_query();
+        ledger.update(&query).await.unwrap();
+        // Only the gas changed; still a new line.
+        query.gas_used += 1;
+        ledger.update(&query).await.unwrap();
+        ledger.update(&query).await.unwrap();
+        assert_eq!(line_count(), 3);
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{\"truncated")
+            .unwrap();
+
+        let reloaded = Ledger::open(&path).await.unwrap();
+        assert_eq!(line_count(), 1);
+        let entry = reloaded.get("01JDKQF9VY2NDBFZAFNFXZC17Z").await.unwrap();
+        assert_eq!(entry.gas_used, Some(query.gas_used));
+    }
+
+    #[tokio::test]
+    async fn test_ledger_rejects_corruption() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger.jsonl");
+        let ledger = Ledger::open(&path).await.unwrap();
+        ledger
+            .record(LedgerEntry::submitted(
+                "01JDKQF9VY2NDBFZAFNFXZC17Z",
+                "/v1/proof-generation",
+                BTreeMap::new(),
+            ))
+            .await
+            .unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        let corrupted = format!("not json\n{}", content);
+        std::fs::write(&path, &corrupted).unwrap();
+
+        assert!(matches!(
+            Ledger::open(&path).await,
+            Err(AtlanticSdkError::CorruptLedger(_))
+        ));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), corrupted);
+    }
+}
@@ -1,18 +1,52 @@
+pub mod account;
+pub mod atlantic_query;
+pub mod batch;
+pub mod billing;
+#[cfg(feature = "calldata")]
+pub mod calldata;
+pub mod environment;
 pub mod error;
+pub mod felt;
+pub mod health;
 pub mod l1_sharp;
 pub mod l2_sharp;
+pub mod ledger;
+#[cfg(feature = "local_trace")]
+pub mod local_trace;
+#[cfg(test)]
+mod mock_server;
 pub mod models;
+pub mod pie;
+pub mod program_input;
+pub mod proof;
 pub mod proof_gen_trace_gen;
+pub mod proof_upload;
+pub mod rate_limit;
+pub mod resubmit;
+pub mod resume;
+#[cfg(feature = "verify")]
+pub mod verify;
+pub mod wait;
+pub mod watcher;
+#[cfg(feature = "webhook")]
+pub mod webhook;
 
 use error::AtlanticSdkError;
-use models::{AtlanticSdk, JobResponse, SharpQueriesResponse, SharpQueryResponse};
+use models::{
+    AtlanticSdk, CancelQueryResult, JobResponse, SharpQueriesResponse, SharpQueryResponse,
+};
+use rate_limit::EndpointKind;
 use tracing::info;
 
 impl AtlanticSdk {
     pub async fn get_is_alive(&self) -> Result<bool, AtlanticSdkError> {
         info!("Checking if SHARP API is alive");
-        let res = reqwest::get(self.health_check.is_alive.clone()).await?;
-        Ok(res.status().is_success())
+        let response = self
+            .client
+            .get(self.health_check.is_alive.clone())
+            .send()
+            .await?;
+        Ok(response.status().is_success())
     }
 
     pub async fn get_sharp_query_jobs(
@@ -21,8 +55,9 @@ impl AtlanticSdk {
     ) -> Result<JobResponse, AtlanticSdkError> {
         info!("Checking job status for sharpQueryId: {}", sharp_query_id);
         let url = format!("{}{}", self.atlantic_queries.get_query_jobs, sharp_query_id);
-        let client = reqwest::Client::new();
-        let response = client
+        self.throttle(EndpointKind::Read).await;
+        let response = self
+            .client
             .get(&url)
             .header("accept", "application/json")
             .send()
@@ -34,10 +69,7 @@ impl AtlanticSdk {
                 let response: JobResponse = serde_json::from_str(&response_text)?;
                 Ok(response)
             }
-            _ => {
-                let response_text = response.text().await?;
-                Err(AtlanticSdkError::CustomError(response_text))
-            }
+            _ => Err(AtlanticSdkError::from_response(response).await),
         }
     }
 
@@ -46,8 +78,9 @@ impl AtlanticSdk {
         sharp_query_id: &str,
     ) -> Result<SharpQueryResponse, AtlanticSdkError> {
         let url = format!("{}{}", self.atlantic_queries.get_query, sharp_query_id);
-        let client = reqwest::Client::new();
-        let response = client
+        self.throttle(EndpointKind::Read).await;
+        let response = self
+            .client
             .get(url)
             .query(&[("apiKey", &self.api_key)])
             .send()
@@ -56,14 +89,41 @@ impl AtlanticSdk {
         match status {
             reqwest::StatusCode::OK => {
                 let response = response.json::<SharpQueryResponse>().await?;
+                self.record_query_details(&response.sharp_query).await;
                 Ok(response)
             }
-            _ => {
-                let response = response.text().await?;
-                Err(AtlanticSdkError::CustomError(response))
+            _ => Err(AtlanticSdkError::from_response(response).await),
+        }
+    }
+    // The cancel endpoint is not in the published API reference yet; the status mapping
+    // below is exercised against the mock server only.
+    pub async fn cancel_query(
+        &self,
+        sharp_query_id: &str,
+    ) -> Result<CancelQueryResult, AtlanticSdkError> {
+        info!("Cancelling sharpQueryId: {}", sharp_query_id);
+        let url = format!("{}{}", self.atlantic_queries.cancel_query, sharp_query_id);
+        self.throttle(EndpointKind::Submit).await;
+        let response = self
+            .client
+            .post(url)
+            .query(&[("apiKey", &self.api_key)])
+            .send()
+            .await?;
+        let status = response.status();
+        match status {
+            reqwest::StatusCode::OK | reqwest::StatusCode::CREATED => {
+                Ok(CancelQueryResult::Cancelled)
+            }
+            reqwest::StatusCode::NOT_FOUND => Ok(CancelQueryResult::NotFound),
+            reqwest::StatusCode::CONFLICT => {
+                let query = self.get_sharp_query(sharp_query_id).await?;
+                Ok(CancelQueryResult::AlreadyFinished(query.sharp_query.status))
             }
+            _ => Err(AtlanticSdkError::from_response(response).await),
         }
     }
+
     pub async fn get_sharp_queries(
         &self,
         limit: Option<u32>,
@@ -83,8 +143,9 @@ impl AtlanticSdk {
             query_params.push((key, value.as_str()));
         }
 
-        let client = reqwest::Client::new();
-        let response = client
+        self.throttle(EndpointKind::Read).await;
+        let response = self
+            .client
             .get(self.atlantic_queries.get_queries.clone())
             .query(&query_params)
             .send()
@@ -93,30 +154,87 @@ impl AtlanticSdk {
         match status {
             reqwest::StatusCode::OK => {
                 let response = response.json::<SharpQueriesResponse>().await?;
+                for query in &response.sharp_queries {
+                    self.record_query_details(query).await;
+                }
                 Ok(response)
             }
-            _ => {
-                let response = response.text().await?;
-                Err(AtlanticSdkError::CustomError(response))
-            }
+            _ => Err(AtlanticSdkError::from_response(response).await),
         }
     }
 
     pub async fn get_proof(&self, query_id: String) -> Result<String, AtlanticSdkError> {
-        let url = format!(
-            "https://atlantic-queries.s3.nl-ams.scw.cloud/sharp_queries/query_{}/proof.json",
-            query_id
-        );
-        let client = reqwest::Client::new();
-        let response = client.get(&url).send().await?;
+        let proof = self
+            .get_artifact(&format!("sharp_queries/query_{}/proof.json", query_id))
+            .await?;
+        String::from_utf8(proof).map_err(|err| {
+            AtlanticSdkError::CustomError(format!(
+                "Proof for query_id {} is not valid UTF-8: {}",
+                query_id, err
+            ))
+        })
+    }
+    pub async fn get_artifact(&self, path: &str) -> Result<Vec<u8>, AtlanticSdkError> {
+        let url = self.artifacts_url.join(path)?;
+        let response = self.client.get(url).send().await?;
         let status = response.status();
         if status != reqwest::StatusCode::OK {
             return Err(AtlanticSdkError::CustomError(format!(
-                "Failed to get proof for query_id: {}. Status: {}",
-                query_id, status
+                "Failed to get artifact {}. Status: {}",
+                path, status
             )));
         }
-        let response_text = response.text().await?;
-        Ok(response_text)
+        Ok(response.bytes().await?.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{extract::Path, http::StatusCode, routing::get, routing::post, Router};
+
+    use super::*;
+    use crate::{
+        mock_server,
+        models::{tests::SHARP_QUERY, CancelQueryResult, QueryStatus},
+    };
+
+    async fn cancel(Path(id): Path<String>) -> StatusCode {
+        match id.as_str() {
+            "running" => StatusCode::OK,
+            "missing" => StatusCode::NOT_FOUND,
+            "finished" => StatusCode::CONFLICT,
+            "forbidden" => StatusCode::FORBIDDEN,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancel_query() {
+        let sdk = mock_server::sdk(
+            Router::new()
+                .route("/v1/atlantic-query-cancel/{id}", post(cancel))
+                .route("/v1/atlantic-query/{id}", get(|| async { SHARP_QUERY })),
+        )
+        .await;
+        assert_eq!(
+            sdk.cancel_query("running").await.unwrap(),
+            CancelQueryResult::Cancelled
+        );
+        assert_eq!(
+            sdk.cancel_query("missing").await.unwrap(),
+            CancelQueryResult::NotFound
+        );
+        assert_eq!(
+            sdk.cancel_query("finished").await.unwrap(),
+            CancelQueryResult::AlreadyFinished(QueryStatus::Done)
+        );
+        assert!(matches!(
+            sdk.cancel_query("forbidden").await,
+            Err(AtlanticSdkError::Unauthorized(_))
+        ));
+        assert!(matches!(
+            sdk.cancel_query("broken").await,
+            Err(AtlanticSdkError::CustomError(_))
+        ));
     }
 }
@@ -0,0 +1,220 @@
+use std::{collections::HashMap, rc::Rc, sync::Arc};
+
+use cairo_vm::{
+    cairo_run::{cairo_run_program_with_initial_scope, CairoRunConfig},
+    hint_processor::{
+        builtin_hint_processor::builtin_hint_processor_definition::{
+            BuiltinHintProcessor, HintFunc,
+        },
+        hint_processor_definition::HintReference,
+    },
+    serde::deserialize_program::ApTracking,
+    types::{exec_scope::ExecutionScopes, layout_name::LayoutName, program::Program},
+    vm::{errors::hint_errors::HintError, vm_core::VirtualMachine},
+    Felt252,
+};
+use tracing::info;
+
+use crate::{error::AtlanticSdkError, models::Layout};
+
+// Rust implementation of a Python hint, run in place of the hint code it is registered for.
+pub type Hint = dyn Fn(
+        &mut VirtualMachine,
+        &mut ExecutionScopes,
+        &HashMap<String, HintReference>,
+        &ApTracking,
+        &HashMap<String, Felt252>,
+    ) -> Result<(), HintError>
+    + Send
+    + Sync;
+
+// Runs compiled Cairo 0 programs in cairo-vm to produce a PIE for `proof_generation`,
+// instead of paying trace credits for `trace_generation`.
+#[derive(Clone)]
+pub struct LocalTraceGenerator {
+    layout: Layout,
+    hints: HashMap<String, Arc<Hint>>,
+}
+
+impl std::fmt::Debug for LocalTraceGenerator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocalTraceGenerator")
+            .field("layout", &self.layout)
+            .field("hints", &self.hints.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Default for LocalTraceGenerator {
+    fn default() -> Self {
+        Self::new(Layout::Auto)
+    }
+}
+
+impl LocalTraceGenerator {
+    pub fn new(layout: Layout) -> Self {
+        Self {
+            layout,
+            hints: HashMap::new(),
+        }
+    }
+
+    // cairo-vm cannot run Python, so hints it does not implement itself, such as those reading
+    // `program_input`, must be registered here under their exact code.
+    pub fn with_hint<F>(mut self, code: impl Into<String>, hint: F) -> Self
+    where
+        F: Fn(
+                &mut VirtualMachine,
+                &mut ExecutionScopes,
+                &HashMap<String, HintReference>,
+                &ApTracking,
+                &HashMap<String, Felt252>,
+            ) -> Result<(), HintError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.hints.insert(code.into(), Arc::new(hint));
+        self
+    }
+
+    // The parsed input is available to hints as `program_input`, a `serde_json::Value`.
+    pub fn generate_pie(
+        &self,
+        program_file: &[u8],
+        input_file: &[u8],
+    ) -> Result<Vec<u8>, AtlanticSdkError> {
+        let failed =
+            |err: &dyn std::fmt::Display| AtlanticSdkError::TraceGenerationFailed(err.to_string());
+        let program = Program::from_bytes(program_file, Some("main")).map_err(|e| failed(&e))?;
+        let mut exec_scopes = ExecutionScopes::new();
+        if !input_file.is_empty() {
+            let input: serde_json::Value = serde_json::from_slice(input_file)?;
+            exec_scopes.insert_value("program_input", input);
+        }
+        let config = CairoRunConfig {
+            layout: layout_name(&self.layout)?,
+            ..CairoRunConfig::default()
+        };
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        for (code, hint) in &self.hints {
+            let hint = hint.clone();
+            hint_processor.add_hint(
+                code.clone(),
+                Rc::new(HintFunc(Box::new(
+                    move |vm, exec_scopes, ids, ap_tracking, constants| {
+                        hint(vm, exec_scopes, ids, ap_tracking, constants)
+                    },
+                ))),
+            );
+        }
+        let runner = cairo_run_program_with_initial_scope(
+            &program,
+            &config,
+            &mut hint_processor,
+            exec_scopes,
+        )
+        .map_err(|e| failed(&e))?;
+        let pie = runner.get_cairo_pie().map_err(|e| failed(&e))?;
+        info!(
+            "Generated PIE locally in {} steps",
+            pie.execution_resources.n_steps
+        );
+
+        // cairo-vm only writes PIEs to files.
+        let dir = tempfile::tempdir()?;
+        let pie_path = dir.path().join("pie.zip");
+        pie.write_zip_file(&pie_path, false)?;
+        Ok(std::fs::read(&pie_path)?)
+    }
+}
+
+fn layout_name(layout: &Layout) -> Result<LayoutName, AtlanticSdkError> {
+    Ok(match layout {
+        Layout::StarknetWithKeccak => LayoutName::starknet_with_keccak,
+        Layout::Recursive => LayoutName::recursive,
+        Layout::Starknet => LayoutName::starknet,
+        Layout::RecursiveWithPoseidon => LayoutName::recursive_with_poseidon,
+        Layout::Dex => LayoutName::dex,
+        Layout::Small => LayoutName::small,
+        // The PIE does not depend on the layout; run with every builtin available.
+        Layout::Auto => LayoutName::all_cairo,
+        Layout::Dynamic => LayoutName::dynamic,
+        Layout::Unknown => {
+            return Err(AtlanticSdkError::TraceGenerationFailed(
+                "unknown layouts are not supported by cairo-vm".to_string(),
+            ))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pie::{MemoryValue, PieMemory, PieMetadata, Relocatable};
+
+    const READ_N_HINT: &str = r#"memory[ap] = program_input["n"]"#;
+
+    fn read_n(vm: &mut VirtualMachine, exec_scopes: &mut ExecutionScopes) -> Result<(), HintError> {
+        let n = exec_scopes
+            .get_ref::<serde_json::Value>("program_input")?
+            .get("n")
+            .and_then(serde_json::Value::as_u64)
+            .ok_or_else(|| HintError::CustomHint("program_input.n is missing".into()))?;
+        vm.insert_value(vm.get_ap(), Felt252::from(n))?;
+        Ok(())
+    }
+
+    fn read_entry(pie: &[u8], name: &str) -> Vec<u8> {
+        let mut zip = zip::ZipArchive::new(std::io::Cursor::new(pie)).unwrap();
+        let mut bytes = Vec::new();
+        std::io::Read::read_to_end(&mut zip.by_name(name).unwrap(), &mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_generate_example_pie() {
+        let program = std::fs::read("examples/compiled_sum.json").unwrap();
+        let pie = LocalTraceGenerator::default()
+            .generate_pie(&program, b"")
+            .unwrap();
+        let mut zip = zip::ZipArchive::new(std::io::Cursor::new(pie)).unwrap();
+        for name in [
+            "metadata.json",
+            "memory.bin",
+            "additional_data.json",
+            "execution_resources.json",
+            "version.json",
+        ] {
+            assert!(zip.by_name(name).is_ok(), "missing {}", name);
+        }
+    }
+
+    #[test]
+    fn test_program_input_hint() {
+        let program = std::fs::read("examples/compiled_square.json").unwrap();
+        let input = std::fs::read("examples/square_input.json").unwrap();
+        // Without a Rust implementation the Python hint cannot run.
+        assert!(matches!(
+            LocalTraceGenerator::default().generate_pie(&program, &input),
+            Err(AtlanticSdkError::TraceGenerationFailed(_))
+        ));
+
+        let pie = LocalTraceGenerator::default()
+            .with_hint(READ_N_HINT, |vm, exec_scopes, _, _, _| {
+                read_n(vm, exec_scopes)
+            })
+            .generate_pie(&program, &input)
+            .unwrap();
+        let metadata: PieMetadata =
+            serde_json::from_slice(&read_entry(&pie, "metadata.json")).unwrap();
+        let memory = PieMemory::from_bytes(&read_entry(&pie, "memory.bin")).unwrap();
+        let output = Relocatable::new(metadata.builtin_segments["output"].index, 0);
+        let value = memory
+            .0
+            .iter()
+            .find(|(address, _)| *address == output)
+            .map(|(_, value)| *value);
+        assert_eq!(value, Some(MemoryValue::Int(crate::felt::Felt::from(144))));
+    }
+}
@@ -0,0 +1,24 @@
+use axum::Router;
+use url::Url;
+
+use crate::models::AtlanticSdk;
+
+// Serves `router` on a local ephemeral port until the test runtime shuts down.
+pub(crate) async fn serve(router: Router) -> Url {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+    Url::parse(&format!("http://{}", address)).unwrap()
+}
+
+pub(crate) async fn sdk(router: Router) -> AtlanticSdk {
+    AtlanticSdk::new("api_key".to_string(), serve(router).await).unwrap()
+}
+
+// A local address nothing listens on.
+pub(crate) async fn closed_url() -> Url {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    drop(listener);
+    Url::parse(&format!("http://{}", address)).unwrap()
+}
@@ -1,9 +1,15 @@
-use std::{fmt::Display, str::FromStr};
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
 
+use chrono::{DateTime, TimeDelta, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use tracing::warn;
 use url::Url;
 
-use crate::error::AtlanticSdkError;
+use crate::{
+    atlantic_query::FilePart, error::AtlanticSdkError, felt::Felt, ledger::Ledger,
+    rate_limit::RateLimiter,
+};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct QueryResponse {
@@ -13,7 +19,7 @@ pub struct QueryResponse {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct FactHashResponse {
     #[serde(rename = "factHash")]
-    pub fact_hash: String,
+    pub fact_hash: Felt,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -30,6 +36,64 @@ pub struct Context {
     pub input_path: Option<String>,
     #[serde(rename = "programPath")]
     pub program_path: Option<String>,
+    // FACT_HASH_GENERATION
+    #[serde(default)]
+    pub child_program_hash: Option<Felt>,
+    #[serde(default)]
+    pub child_output: Option<Vec<Felt>>,
+    #[serde(default)]
+    pub bootloader_output: Option<Vec<Felt>>,
+    #[serde(default)]
+    pub bootloader_output_hash: Option<Felt>,
+    #[serde(default)]
+    pub bootloader_program_hash: Option<Felt>,
+    #[serde(default)]
+    pub fact_hash: Option<Felt>,
+    // PROOF_VERIFICATION
+    #[serde(rename = "numberOfSteps")]
+    pub number_of_steps: Option<u64>,
+    pub hasher: Option<String>,
+    pub initial: Option<VerificationTransaction>,
+    #[serde(rename = "final")]
+    pub final_: Option<VerificationTransaction>,
+    // `step1`..`stepN` of the split verification and fields not modelled above.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+impl Context {
+    // Verification transactions in submission order: initial, step1..stepN, final.
+    pub fn verification_transactions(&self) -> Vec<(String, VerificationTransaction)> {
+        let mut steps: Vec<(u32, VerificationTransaction)> = self
+            .extra
+            .iter()
+            .filter_map(|(key, value)| {
+                let step = key.strip_prefix("step")?.parse().ok()?;
+                Some((step, serde_json::from_value(value.clone()).ok()?))
+            })
+            .collect();
+        steps.sort_by_key(|(step, _)| *step);
+        self.initial
+            .clone()
+            .map(|tx| ("initial".to_string(), tx))
+            .into_iter()
+            .chain(
+                steps
+                    .into_iter()
+                    .map(|(step, tx)| (format!("step{}", step), tx)),
+            )
+            .chain(self.final_.clone().map(|tx| ("final".to_string(), tx)))
+            .collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct VerificationTransaction {
+    #[serde(rename = "transactionHash")]
+    pub transaction_hash: Felt,
+    pub price: Decimal,
+    #[serde(rename = "gasAmount")]
+    pub gas_amount: u64,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -41,9 +105,9 @@ pub struct Job {
     #[serde(rename = "jobName")]
     pub job_name: String,
     #[serde(rename = "createdAt")]
-    pub created_at: String,
+    pub created_at: DateTime<Utc>,
     #[serde(rename = "completedAt")]
-    pub completed_at: Option<String>,
+    pub completed_at: Option<DateTime<Utc>>,
     pub context: Option<Context>,
 }
 
@@ -53,38 +117,85 @@ pub struct JobResponse {
     pub steps: Vec<String>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+impl Job {
+    // None while the job is still running.
+    pub fn duration(&self) -> Option<TimeDelta> {
+        self.completed_at
+            .map(|completed_at| completed_at - self.created_at)
+    }
+}
+
+impl JobResponse {
+    // Time spent in each completed job, by job name, in the order the jobs ran.
+    pub fn step_durations(&self) -> Vec<(&str, TimeDelta)> {
+        self.jobs_by_start()
+            .into_iter()
+            .filter_map(|job| Some((job.job_name.as_str(), job.duration()?)))
+            .collect()
+    }
+
+    // Time each job waited in the queue after the previous job completed. Jobs that started
+    // before the previous one finished, such as retries or overlapping jobs, waited zero.
+    pub fn queue_waits(&self) -> Vec<(&str, TimeDelta)> {
+        self.jobs_by_start()
+            .windows(2)
+            .filter_map(|pair| {
+                let waited = pair[1].created_at - pair[0].completed_at?;
+                Some((pair[1].job_name.as_str(), waited.max(TimeDelta::zero())))
+            })
+            .collect()
+    }
+
+    fn jobs_by_start(&self) -> Vec<&Job> {
+        let mut jobs: Vec<&Job> = self.jobs.iter().collect();
+        jobs.sort_by_key(|job| job.created_at);
+        jobs
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct SharpQueryDetails {
     pub id: String,
-    #[serde(rename = "externalId")]
+    // Omitted by the API for queries submitted without an external ID.
+    #[serde(rename = "externalId", default)]
     pub external_id: String,
     #[serde(rename = "submittedByClient")]
     pub submitted_by_client: String,
-    pub status: String,
+    pub status: QueryStatus,
     pub step: Option<String>,
-    #[serde(rename = "programHash")]
-    pub program_hash: Option<String>,
-    pub layout: Option<String>,
-    #[serde(rename = "programFactHash")]
-    pub program_fact_hash: Option<String>,
-    pub price: String,
+    #[serde(rename = "programHash", default)]
+    pub program_hash: Option<Felt>,
+    #[serde(default, deserialize_with = "deserialize_layout_or_unknown")]
+    pub layout: Option<Layout>,
+    #[serde(rename = "programFactHash", default)]
+    pub program_fact_hash: Option<Felt>,
+    // Accepted as a decimal string or number, serialized as a string.
+    pub price: Decimal,
     #[serde(rename = "gasUsed")]
-    pub gas_used: usize,
+    pub gas_used: u64,
     #[serde(rename = "creditsUsed")]
-    pub credits_used: usize,
+    pub credits_used: u64,
     #[serde(rename = "traceCreditsUsed")]
-    pub trace_credits_used: usize,
+    pub trace_credits_used: u64,
     #[serde(rename = "isFactMocked")]
     pub is_fact_mocked: Option<bool>,
-    pub prover: Option<String>,
-    pub chain: Option<String>,
+    pub prover: Option<ProverVersion>,
+    pub chain: Option<Chain>,
     pub steps: Vec<String>,
     #[serde(rename = "createdAt")]
-    pub created_at: String,
+    pub created_at: DateTime<Utc>,
     #[serde(rename = "completedAt")]
-    pub completed_at: Option<String>,
+    pub completed_at: Option<DateTime<Utc>>,
 }
-#[derive(Deserialize, Serialize, Debug)]
+
+impl SharpQueryDetails {
+    // Time from submission to completion; None while the query is running.
+    pub fn duration(&self) -> Option<TimeDelta> {
+        self.completed_at
+            .map(|completed_at| completed_at - self.created_at)
+    }
+}
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct SharpQueryResponse {
     #[serde(rename = "atlanticQuery")]
     pub sharp_query: SharpQueryDetails,
@@ -95,16 +206,156 @@ pub struct SharpQueriesResponse {
     pub sharp_queries: Vec<SharpQueryDetails>, // List of SharpQueryDetails
     pub total: usize, // Total count of queries
 }
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum QueryStatus {
+    Received,
+    InProgress,
+    Done,
+    Failed,
+    Cancelled,
+    #[serde(other)]
+    Unknown,
+}
+impl QueryStatus {
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            QueryStatus::Done | QueryStatus::Failed | QueryStatus::Cancelled
+        )
+    }
+}
+impl Display for QueryStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueryStatus::Received => write!(f, "RECEIVED"),
+            QueryStatus::InProgress => write!(f, "IN_PROGRESS"),
+            QueryStatus::Done => write!(f, "DONE"),
+            QueryStatus::Failed => write!(f, "FAILED"),
+            QueryStatus::Cancelled => write!(f, "CANCELLED"),
+            QueryStatus::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum QueryStep {
+    TraceGeneration,
+    ProofGeneration,
+    FactHashGeneration,
+    ProofVerification,
+}
+impl QueryStep {
+    pub const ALL: [QueryStep; 4] = [
+        QueryStep::TraceGeneration,
+        QueryStep::ProofGeneration,
+        QueryStep::FactHashGeneration,
+        QueryStep::ProofVerification,
+    ];
+    pub fn iter() -> impl Iterator<Item = QueryStep> {
+        Self::ALL.into_iter()
+    }
+}
+impl Display for QueryStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueryStep::TraceGeneration => write!(f, "TRACE_GENERATION"),
+            QueryStep::ProofGeneration => write!(f, "PROOF_GENERATION"),
+            QueryStep::FactHashGeneration => write!(f, "FACT_HASH_GENERATION"),
+            QueryStep::ProofVerification => write!(f, "PROOF_VERIFICATION"),
+        }
+    }
+}
+impl FromStr for QueryStep {
+    type Err = AtlanticSdkError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_variant("step", s, &Self::ALL)
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CancelQueryResult {
+    Cancelled,
+    // The query reached a terminal status before it could be cancelled.
+    AlreadyFinished(QueryStatus),
+    NotFound,
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ProverVersion {
     Starkware,
+    Stone,
+    Stwo,
+    // Reported by the API but not known to this client; never parsed from user input and
+    // rejected in submissions.
+    Unknown,
+}
+impl ProverVersion {
+    pub const ALL: [ProverVersion; 3] = [
+        ProverVersion::Starkware,
+        ProverVersion::Stone,
+        ProverVersion::Stwo,
+    ];
+    pub fn iter() -> impl Iterator<Item = ProverVersion> {
+        Self::ALL.into_iter()
+    }
+    pub fn ensure_known(&self) -> Result<(), AtlanticSdkError> {
+        ensure_known("prover", self, &ProverVersion::Unknown, &Self::ALL)
+    }
 }
 impl Display for ProverVersion {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ProverVersion::Starkware => write!(f, "starkware_sharp"),
+            ProverVersion::Stone => write!(f, "herodotus_stone"),
+            ProverVersion::Stwo => write!(f, "herodotus_stwo"),
+            ProverVersion::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+impl FromStr for ProverVersion {
+    type Err = AtlanticSdkError;
+    // The API reports provers in upper case (e.g. `STARKWARE_SHARP`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_variant("prover", s, &Self::ALL)
+    }
+}
+impl Serialize for ProverVersion {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+// Provers added on the server side must not break deserialization of whole query lists.
+impl<'de> Deserialize<'de> for ProverVersion {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(s.parse().unwrap_or_else(|_| {
+            warn!("Unknown prover {} in query details", s);
+            ProverVersion::Unknown
+        }))
+    }
+}
+impl ProverVersion {
+    pub fn supports_layout(&self, layout: &Layout) -> bool {
+        match self {
+            ProverVersion::Starkware | ProverVersion::Stone => true,
+            // Stwo does not run on Stone layouts, Atlantic picks its configuration.
+            ProverVersion::Stwo => matches!(layout, Layout::Auto),
+            ProverVersion::Unknown => false,
+        }
+    }
+    pub fn validate_layout(&self, layout: &Layout) -> Result<(), AtlanticSdkError> {
+        self.ensure_known()?;
+        layout.ensure_known()?;
+        if self.supports_layout(layout) {
+            Ok(())
+        } else {
+            Err(AtlanticSdkError::UnsupportedLayout {
+                prover: self.to_string(),
+                layout: layout.to_string(),
+            })
         }
     }
 }
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Layout {
     StarknetWithKeccak,
     Recursive,
@@ -114,6 +365,27 @@ pub enum Layout {
     Small,
     Auto,
     Dynamic,
+    // Reported by the API but not known to this client; never parsed from user input and
+    // rejected in submissions.
+    Unknown,
+}
+impl Layout {
+    pub const ALL: [Layout; 8] = [
+        Layout::StarknetWithKeccak,
+        Layout::Recursive,
+        Layout::Starknet,
+        Layout::RecursiveWithPoseidon,
+        Layout::Dex,
+        Layout::Small,
+        Layout::Auto,
+        Layout::Dynamic,
+    ];
+    pub fn iter() -> impl Iterator<Item = Layout> {
+        Self::ALL.into_iter()
+    }
+    pub fn ensure_known(&self) -> Result<(), AtlanticSdkError> {
+        ensure_known("layout", self, &Layout::Unknown, &Self::ALL)
+    }
 }
 impl Display for Layout {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -126,32 +398,279 @@ impl Display for Layout {
             Layout::Auto => write!(f, "auto"),
             Layout::Small => write!(f, "small"),
             Layout::Dynamic => write!(f, "dynamic"),
+            Layout::Unknown => write!(f, "unknown"),
         }
     }
 }
 impl FromStr for Layout {
     type Err = AtlanticSdkError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "starknet_with_keccak" => Ok(Layout::StarknetWithKeccak),
-            "recursive" => Ok(Layout::Recursive),
-            "starknet" => Ok(Layout::Starknet),
-            "recursive_with_poseidon" => Ok(Layout::RecursiveWithPoseidon),
-            "dex" => Ok(Layout::Dex),
-            "auto" => Ok(Layout::Auto),
-            "small" => Ok(Layout::Small),
-            "dynamic" => Ok(Layout::Dynamic),
-            _ => Err(AtlanticSdkError::InvalidLayout),
-        }
+        parse_variant("layout", s, &Self::ALL)
     }
 }
+impl Serialize for Layout {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+impl<'de> Deserialize<'de> for Layout {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+// Layouts added on the server side must not break deserialization of whole query lists.
+fn deserialize_layout_or_unknown<'de, D>(deserializer: D) -> Result<Option<Layout>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let layout = Option::<String>::deserialize(deserializer)?;
+    Ok(layout.map(|layout| {
+        layout.parse().unwrap_or_else(|_| {
+            warn!("Unknown layout {} in query details", layout);
+            Layout::Unknown
+        })
+    }))
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CairoVersion {
     Zero,
+    One,
+}
+impl CairoVersion {
+    pub const ALL: [CairoVersion; 2] = [CairoVersion::Zero, CairoVersion::One];
+    pub fn iter() -> impl Iterator<Item = CairoVersion> {
+        Self::ALL.into_iter()
+    }
 }
 impl Display for CairoVersion {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             CairoVersion::Zero => write!(f, "0"),
+            CairoVersion::One => write!(f, "1"),
+        }
+    }
+}
+impl FromStr for CairoVersion {
+    type Err = AtlanticSdkError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_variant("cairo version", s, &Self::ALL)
+    }
+}
+impl TryFrom<u64> for CairoVersion {
+    type Error = AtlanticSdkError;
+    fn try_from(version: u64) -> Result<Self, Self::Error> {
+        match version {
+            0 => Ok(CairoVersion::Zero),
+            1 => Ok(CairoVersion::One),
+            _ => Err(AtlanticSdkError::InvalidVariant {
+                kind: "cairo version",
+                value: version.to_string(),
+                suggestion: None,
+                expected: "0, 1".to_string(),
+            }),
+        }
+    }
+}
+// The API reports the version as a number (`"cairoVersion": 0`).
+impl Serialize for CairoVersion {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            CairoVersion::Zero => serializer.serialize_u8(0),
+            CairoVersion::One => serializer.serialize_u8(1),
+        }
+    }
+}
+impl<'de> Deserialize<'de> for CairoVersion {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Number(u64),
+            Text(String),
+        }
+        match Raw::deserialize(deserializer)? {
+            Raw::Number(n) => CairoVersion::try_from(n),
+            Raw::Text(s) => s.parse(),
+        }
+        .map_err(serde::de::Error::custom)
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Chain {
+    L1,
+    L2,
+    // Reported by the API but not known to this client; never parsed from user input and
+    // rejected in submissions.
+    Unknown,
+}
+impl Chain {
+    pub const ALL: [Chain; 2] = [Chain::L1, Chain::L2];
+    pub fn iter() -> impl Iterator<Item = Chain> {
+        Self::ALL.into_iter()
+    }
+    pub fn ensure_known(&self) -> Result<(), AtlanticSdkError> {
+        ensure_known("chain", self, &Chain::Unknown, &Self::ALL)
+    }
+}
+impl Display for Chain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Chain::L1 => write!(f, "L1"),
+            Chain::L2 => write!(f, "L2"),
+            Chain::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+impl FromStr for Chain {
+    type Err = AtlanticSdkError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_variant("chain", s, &Self::ALL)
+    }
+}
+impl Serialize for Chain {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+// Chains added on the server side must not break deserialization of whole query lists.
+impl<'de> Deserialize<'de> for Chain {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(s.parse().unwrap_or_else(|_| {
+            warn!("Unknown chain {} in query details", s);
+            Chain::Unknown
+        }))
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Network {
+    Mainnet,
+    Sepolia,
+}
+impl Network {
+    pub const ALL: [Network; 2] = [Network::Mainnet, Network::Sepolia];
+    pub fn iter() -> impl Iterator<Item = Network> {
+        Self::ALL.into_iter()
+    }
+}
+impl Display for Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Network::Mainnet => write!(f, "mainnet"),
+            Network::Sepolia => write!(f, "sepolia"),
+        }
+    }
+}
+impl FromStr for Network {
+    type Err = AtlanticSdkError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_variant("network", s, &Self::ALL)
+    }
+}
+impl Serialize for Network {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+impl<'de> Deserialize<'de> for Network {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+pub(crate) fn parse_variant<T: Clone + Display>(
+    kind: &'static str,
+    s: &str,
+    variants: &[T],
+) -> Result<T, AtlanticSdkError> {
+    let input = s.trim().to_ascii_lowercase();
+    if let Some(variant) = variants
+        .iter()
+        .find(|v| v.to_string().to_ascii_lowercase() == input)
+    {
+        return Ok(variant.clone());
+    }
+    let suggestion = variants
+        .iter()
+        .map(|v| v.to_string())
+        .map(|v| (edit_distance(&input, &v.to_ascii_lowercase()), v))
+        .filter(|(distance, v)| *distance <= v.len() / 3)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, v)| v);
+    Err(AtlanticSdkError::InvalidVariant {
+        kind,
+        value: s.to_string(),
+        suggestion,
+        expected: variants
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+            .join(", "),
+    })
+}
+// `Unknown` variants only come from API responses and are never sent back to the server.
+fn ensure_known<T: Display + PartialEq>(
+    kind: &'static str,
+    value: &T,
+    unknown: &T,
+    variants: &[T],
+) -> Result<(), AtlanticSdkError> {
+    if value != unknown {
+        return Ok(());
+    }
+    Err(AtlanticSdkError::InvalidVariant {
+        kind,
+        value: value.to_string(),
+        suggestion: None,
+        expected: variants
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+            .join(", "),
+    })
+}
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+impl CairoVersion {
+    // Every program is uploaded the same way, the server tells them apart by `cairoVersion`.
+    // Cairo 1 programs are only checked to be Sierra or CASM executables before upload.
+    pub(crate) fn program_part(&self, program_file: Vec<u8>) -> Result<FilePart, AtlanticSdkError> {
+        if *self == CairoVersion::One {
+            Cairo1Program::detect(&program_file)?;
+        }
+        Ok(FilePart::new(
+            program_file,
+            "program.json",
+            "application/json",
+        ))
+    }
+}
+#[derive(Debug, PartialEq)]
+enum Cairo1Program {
+    Sierra,
+    Casm,
+}
+impl Cairo1Program {
+    fn detect(program_file: &[u8]) -> Result<Self, AtlanticSdkError> {
+        let program: serde_json::Value = serde_json::from_slice(program_file)?;
+        if program.get("sierra_program").is_some() {
+            Ok(Cairo1Program::Sierra)
+        } else if program.get("bytecode").is_some() {
+            Ok(Cairo1Program::Casm)
+        } else {
+            Err(AtlanticSdkError::InvalidProgramFile)
         }
     }
 }
@@ -159,6 +678,16 @@ impl Display for CairoVersion {
 
 pub struct AtlanticSdk {
     pub api_key: String,
+    // Sent with submissions only when set, otherwise the server's default applies.
+    pub network: Option<Network>,
+    pub artifacts_url: Url,
+    pub client: reqwest::Client,
+    pub rate_limiter: Option<RateLimiter>,
+    // Registered with every query submission so Atlantic calls it back on completion.
+    pub callback_url: Option<Url>,
+    pub ledger: Option<Ledger>,
+    // How often a submission rejected with 429 is resent, see `submit_batch`.
+    pub(crate) rate_limit_retries: u32,
     pub l1: L1Endpoints,
     pub l2: L2Endpoints,
     pub proof_generation_trace_generation: ProofGenTraceGenEndpoints,
@@ -190,6 +719,7 @@ pub struct AtlanticQueriesEndpoints {
     pub get_queries: Url,
     pub get_query: Url,
     pub get_query_jobs: Url,
+    pub cancel_query: Url,
 }
 #[derive(Debug, Clone)]
 pub struct HealthCheckEndpoint {
@@ -204,6 +734,13 @@ impl AtlanticSdk {
     pub fn new(api_key: String, base_url: Url) -> Result<Self, url::ParseError> {
         Ok(Self {
             api_key,
+            network: None,
+            artifacts_url: Url::parse("https://atlantic-queries.s3.nl-ams.scw.cloud/")?,
+            client: reqwest::Client::new(),
+            rate_limiter: None,
+            callback_url: None,
+            ledger: None,
+            rate_limit_retries: 0,
             l1: L1Endpoints {
                 atlantic_query: base_url.join("/v1/l1/atlantic-query")?,
                 proof_generation_verification: base_url
@@ -226,6 +763,7 @@ impl AtlanticSdk {
                 get_queries: base_url.join("/v1/atlantic-queries")?,
                 get_query: base_url.join("/v1/atlantic-query/")?,
                 get_query_jobs: base_url.join("/v1/atlantic-query-jobs/")?,
+                cancel_query: base_url.join("/v1/atlantic-query-cancel/")?,
             },
             health_check: HealthCheckEndpoint {
                 is_alive: base_url.join("/v1/is-alive")?,
@@ -235,38 +773,60 @@ impl AtlanticSdk {
             },
         })
     }
+    pub fn with_network(mut self, network: Network) -> Self {
+        self.network = Some(network);
+        self
+    }
+    pub fn with_callback_url(mut self, callback_url: Url) -> Self {
+        self.callback_url = Some(callback_url);
+        self
+    }
+    pub fn with_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
     use url::Url;
 
-    #[test]
-    fn test_new() {
-        let api_key = "api_key".to_string();
-        let base_url = Url::parse("http://test_url:8080").unwrap();
-        let sdk = AtlanticSdk::new(api_key.clone(), base_url.clone()).unwrap();
-        assert_eq!(sdk.api_key, api_key);
-        assert_eq!(
-            sdk.l1.atlantic_query,
-            Url::parse("http://test_url:8080/v1/l1/atlantic-query").unwrap()
-        );
-    }
-    #[test]
-    fn test_new_with_slash() {
-        let api_key = "api_key".to_string();
-        let base_url = Url::parse("http://test_url:8080/").unwrap();
-        let sdk = AtlanticSdk::new(api_key.clone(), base_url.clone()).unwrap();
-        assert_eq!(sdk.api_key, api_key);
-        assert_eq!(
-            sdk.l1.atlantic_query,
-            Url::parse("http://test_url:8080/v1/l1/atlantic-query").unwrap()
-        );
+    // API responses shared with the tests of other modules.
+    pub(crate) const SHARP_QUERY: &str = r#"
+            {
+                "atlanticQuery": {
+                    "id": "01JDKQF9VY2NDBFZAFNFXZC17Z",
+                    "submittedByClient": "01J8M351V7NV7QRQYCS54RKFYD",
+                    "status": "DONE",
+                    "step": "PROOF_VERIFICATION",
+                    "programHash": "0x193641eb151b0f41674641089952e60bc3aded26e3cf42793655c562b8c3aa0",
+                    "layout": "recursive_with_poseidon",
+                    "programFactHash": "0x46997b155c917870ee90724b9d0a42d2fac9bb60f6ebbe2c700aa7495d028bf",
+                    "price": "0.85",
+                    "gasUsed": 14605,
+                    "creditsUsed": 0,
+                    "traceCreditsUsed": 0,
+                    "isFactMocked": false,
+                    "chain": "L2",
+                    "prover": "STARKWARE_SHARP",
+                    "steps": [
+                    "TRACE_GENERATION",
+                    "PROOF_GENERATION",
+                    "FACT_HASH_GENERATION",
+                    "PROOF_VERIFICATION"
+                    ],
+                    "createdAt": "2024-11-26T07:55:21.124Z",
+                    "completedAt": "2024-11-26T08:46:05.375Z"
+                }
+            }
+        "#;
+    pub(crate) fn sharp_query() -> SharpQueryDetails {
+        serde_json::from_str::<SharpQueryResponse>(SHARP_QUERY)
+            .unwrap()
+            .sharp_query
     }
-    #[test]
-    fn test_deserialize_jobs() {
-        let response = r#"{
+    pub(crate) const SHARP_QUERY_JOBS: &str = r#"{
             "jobs": [
                 {
                 "id": "f01b3b3c-a35b-4ffb-a7ee-1c14334ab95c",
@@ -389,47 +949,7 @@ mod tests {
                 "PROOF_VERIFICATION"
             ]
         }"#;
-        let job_response: JobResponse = serde_json::from_str(response).unwrap();
-        assert_eq!(job_response.jobs.len(), 4);
-        assert_eq!(job_response.steps.len(), 4);
-    }
-    #[test]
-    fn test_deserialize_query() {
-        let query = r#"
-            {
-                "atlanticQuery": {
-                    "id": "01JDKQF9VY2NDBFZAFNFXZC17Z",
-                    "submittedByClient": "01J8M351V7NV7QRQYCS54RKFYD",
-                    "status": "DONE",
-                    "step": "PROOF_VERIFICATION",
-                    "programHash": "0x193641eb151b0f41674641089952e60bc3aded26e3cf42793655c562b8c3aa0",
-                    "layout": "recursive_with_poseidon",
-                    "programFactHash": "0x46997b155c917870ee90724b9d0a42d2fac9bb60f6ebbe2c700aa7495d028bf",
-                    "price": "0.85",
-                    "gasUsed": 14605,
-                    "creditsUsed": 0,
-                    "traceCreditsUsed": 0,
-                    "isFactMocked": false,
-                    "chain": "L2",
-                    "prover": "STARKWARE_SHARP",
-                    "steps": [
-                    "TRACE_GENERATION",
-                    "PROOF_GENERATION",
-                    "FACT_HASH_GENERATION",
-                    "PROOF_VERIFICATION"
-                    ],
-                    "createdAt": "2024-11-26T07:55:21.124Z",
-                    "completedAt": "2024-11-26T08:46:05.375Z"
-                }
-            }
-        "#;
-        let query_response: SharpQueryResponse = serde_json::from_str(query).unwrap();
-        assert_eq!(query_response.sharp_query.id, "01JDKQF9VY2NDBFZAFNFXZC17Z");
-        println!("{:#?}", query_response);
-    }
-    #[test]
-    fn test_deserialize_atlantic_queries() {
-        let queries = r#"
+    pub(crate) const SHARP_QUERIES: &str = r#"
         {
             "sharpQueries": [
                 {
@@ -483,7 +1003,222 @@ mod tests {
             ],
             "total": 148
         }"#;
-        let queries_response: SharpQueriesResponse = serde_json::from_str(queries).unwrap();
+
+    #[test]
+    fn test_new() {
+        let api_key = "api_key".to_string();
+        let base_url = Url::parse("http://test_url:8080").unwrap();
+        let sdk = AtlanticSdk::new(api_key.clone(), base_url.clone()).unwrap();
+        assert_eq!(sdk.api_key, api_key);
+        assert_eq!(
+            sdk.l1.atlantic_query,
+            Url::parse("http://test_url:8080/v1/l1/atlantic-query").unwrap()
+        );
+    }
+    #[test]
+    fn test_new_with_slash() {
+        let api_key = "api_key".to_string();
+        let base_url = Url::parse("http://test_url:8080/").unwrap();
+        let sdk = AtlanticSdk::new(api_key.clone(), base_url.clone()).unwrap();
+        assert_eq!(sdk.api_key, api_key);
+        assert_eq!(
+            sdk.l1.atlantic_query,
+            Url::parse("http://test_url:8080/v1/l1/atlantic-query").unwrap()
+        );
+    }
+    #[test]
+    fn test_detect_cairo1_program() {
+        let sierra = br#"{"sierra_program": [], "entry_points_by_type": {}}"#;
+        let casm = br#"{"prime": "0x800000000000011000000000000000000000000000000000000000000000001", "bytecode": []}"#;
+        assert_eq!(
+            Cairo1Program::detect(sierra).unwrap(),
+            Cairo1Program::Sierra
+        );
+        assert_eq!(Cairo1Program::detect(casm).unwrap(), Cairo1Program::Casm);
+        assert!(matches!(
+            Cairo1Program::detect(br#"{"data": []}"#),
+            Err(AtlanticSdkError::InvalidProgramFile)
+        ));
+    }
+    #[test]
+    fn test_prover_version() {
+        assert_eq!(
+            ProverVersion::from_str("STARKWARE_SHARP").unwrap(),
+            ProverVersion::Starkware
+        );
+        assert_eq!(
+            serde_json::to_string(&ProverVersion::Stone).unwrap(),
+            r#""herodotus_stone""#
+        );
+        assert!(ProverVersion::from_str("unknown").is_err());
+        assert_eq!(
+            serde_json::from_str::<ProverVersion>(r#""HERODOTUS_FUTURE_PROVER""#).unwrap(),
+            ProverVersion::Unknown
+        );
+        assert!(ProverVersion::Unknown
+            .validate_layout(&Layout::Recursive)
+            .is_err());
+        assert!(ProverVersion::Stwo.validate_layout(&Layout::Auto).is_ok());
+        assert!(ProverVersion::Stwo
+            .validate_layout(&Layout::RecursiveWithPoseidon)
+            .is_err());
+    }
+    #[test]
+    fn test_parse_enums() {
+        for layout in Layout::iter() {
+            assert_eq!(Layout::from_str(&layout.to_string()).unwrap(), layout);
+        }
+        for cairo_version in CairoVersion::iter() {
+            let json = serde_json::to_string(&cairo_version).unwrap();
+            assert_eq!(
+                serde_json::from_str::<CairoVersion>(&json).unwrap(),
+                cairo_version
+            );
+        }
+        assert_eq!(
+            Layout::from_str("Recursive_With_Poseidon").unwrap(),
+            Layout::RecursiveWithPoseidon
+        );
+        let err = Layout::from_str("recursive_with_posiedon").unwrap_err();
+        assert!(matches!(
+            err,
+            AtlanticSdkError::InvalidVariant {
+                suggestion: Some(ref s),
+                ..
+            } if s == "recursive_with_poseidon"
+        ));
+        assert_eq!(Chain::from_str("l1").unwrap(), Chain::L1);
+        assert!(Chain::from_str("L3").is_err());
+        assert_eq!(
+            serde_json::from_str::<Chain>(r#""L3""#).unwrap(),
+            Chain::Unknown
+        );
+        assert_eq!(Network::from_str("Sepolia").unwrap(), Network::Sepolia);
+        assert!(matches!(
+            Layout::from_str("foo").unwrap_err(),
+            AtlanticSdkError::InvalidVariant {
+                suggestion: None,
+                ..
+            }
+        ));
+    }
+    #[test]
+    fn test_unknown_layout_in_query() {
+        let details = r#"{
+            "id": "01JDKQF9VY2NDBFZAFNFXZC17Z",
+            "submittedByClient": "01J8M351V7NV7QRQYCS54RKFYD",
+            "status": "DONE",
+            "layout": "some_future_layout",
+            "price": "0.85",
+            "gasUsed": 0,
+            "creditsUsed": 0,
+            "traceCreditsUsed": 0,
+            "steps": [],
+            "createdAt": "2024-11-26T07:55:21.124Z"
+        }"#;
+        let details: SharpQueryDetails = serde_json::from_str(details).unwrap();
+        assert_eq!(details.layout, Some(Layout::Unknown));
+        assert!(Layout::from_str("some_future_layout").is_err());
+        // Never sent back, e.g. when resubmitting the query.
+        assert!(matches!(
+            ProverVersion::Starkware.validate_layout(&Layout::Unknown),
+            Err(AtlanticSdkError::InvalidVariant { kind: "layout", .. })
+        ));
+        assert!(Chain::Unknown.ensure_known().is_err());
+        assert!(Chain::L2.ensure_known().is_ok());
+    }
+    #[test]
+    fn test_malformed_hash_in_query() {
+        let hash = "\"0x193641eb151b0f41674641089952e60bc3aded26e3cf42793655c562b8c3aa0\"";
+        let big = "340282366920938463463374607431768211456";
+        for malformed in ["\"not a hash\"", big] {
+            let query = SHARP_QUERY.replace(hash, malformed);
+            assert!(serde_json::from_str::<SharpQueryResponse>(&query).is_err());
+        }
+        let query = SHARP_QUERY.replace(hash, &format!("\"{}\"", big));
+        let details = serde_json::from_str::<SharpQueryResponse>(&query)
+            .unwrap()
+            .sharp_query;
+        assert_eq!(details.program_hash, Some(big.parse().unwrap()));
+    }
+    #[test]
+    fn test_deserialize_jobs() {
+        let job_response: JobResponse = serde_json::from_str(SHARP_QUERY_JOBS).unwrap();
+        assert_eq!(job_response.jobs.len(), 4);
+        assert_eq!(job_response.steps.len(), 4);
+        let fact_hash_context = job_response.jobs[2].context.as_ref().unwrap();
+        assert_eq!(
+            fact_hash_context.fact_hash,
+            Some(
+                "0x046997b155c917870ee90724b9d0a42d2fac9bb60f6ebbe2c700aa7495d028bf"
+                    .parse()
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            fact_hash_context.bootloader_output.as_ref().unwrap().len(),
+            5
+        );
+        let transactions = job_response.jobs[3]
+            .context
+            .as_ref()
+            .unwrap()
+            .verification_transactions();
+        assert_eq!(transactions.len(), 10);
+        assert_eq!(transactions[1].0, "step1");
+        assert_eq!(transactions[9].0, "final");
+        assert_eq!(transactions[9].1.gas_amount, 212);
+        assert_eq!(
+            job_response.jobs[3]
+                .context
+                .as_ref()
+                .unwrap()
+                .verification_price(),
+            Decimal::new(85, 2)
+        );
+
+        let durations = job_response.step_durations();
+        assert_eq!(durations[0].0, "TRACE_GENERATION");
+        assert_eq!(durations[0].1, TimeDelta::milliseconds(537_124));
+        let waits = job_response.queue_waits();
+        assert_eq!(waits.len(), 3);
+        assert_eq!(waits[0], ("PROOF_GENERATION", TimeDelta::milliseconds(323)));
+    }
+    #[test]
+    fn test_queue_waits_of_overlapping_jobs() {
+        let mut jobs: serde_json::Value = serde_json::from_str(SHARP_QUERY_JOBS).unwrap();
+        // PROOF_GENERATION retried before TRACE_GENERATION was marked completed.
+        jobs["jobs"][1]["createdAt"] = "2024-11-26T08:00:00.000Z".into();
+        let job_response: JobResponse = serde_json::from_value(jobs).unwrap();
+        let waits = job_response.queue_waits();
+        assert_eq!(waits[0], ("PROOF_GENERATION", TimeDelta::zero()));
+        assert!(waits.iter().all(|(_, waited)| *waited >= TimeDelta::zero()));
+    }
+    #[test]
+    fn test_deserialize_query() {
+        let query_response: SharpQueryResponse = serde_json::from_str(SHARP_QUERY).unwrap();
+        assert_eq!(query_response.sharp_query.id, "01JDKQF9VY2NDBFZAFNFXZC17Z");
+        assert_eq!(query_response.sharp_query.external_id, "");
+        assert_eq!(
+            query_response.sharp_query.prover,
+            Some(ProverVersion::Starkware)
+        );
+        assert_eq!(
+            query_response.sharp_query.layout,
+            Some(Layout::RecursiveWithPoseidon)
+        );
+        assert_eq!(query_response.sharp_query.chain, Some(Chain::L2));
+        assert_eq!(query_response.sharp_query.status, QueryStatus::Done);
+        assert!(query_response.sharp_query.status.is_terminal());
+        assert_eq!(
+            query_response.sharp_query.duration(),
+            Some(TimeDelta::milliseconds(3_044_251))
+        );
+        println!("{:#?}", query_response);
+    }
+    #[test]
+    fn test_deserialize_atlantic_queries() {
+        let queries_response: SharpQueriesResponse = serde_json::from_str(SHARP_QUERIES).unwrap();
         println!("{:#?}", queries_response);
     }
 }
@@ -0,0 +1,398 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{Cursor, Write},
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::value::RawValue;
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+use crate::{error::AtlanticSdkError, felt::Felt};
+
+const PRIME: &str = "3618502788666131213697322783095070105623107215331596699973092056135872020481";
+pub const PIE_VERSION: &str = "1.1";
+
+// memory.bin layout: 8 byte address followed by a 32 byte value, both little-endian.
+const ADDR_BYTE_LEN: usize = 8;
+const FIELD_BYTE_LEN: usize = 32;
+const CELL_BYTE_LEN: usize = ADDR_BYTE_LEN + FIELD_BYTE_LEN;
+const ADDR_BASE: u64 = 1 << 63;
+const OFFSET_BIT_LEN: u32 = 47;
+const RELOCATABLE_FLAG: u8 = 0x80;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Relocatable {
+    pub segment_index: usize,
+    pub offset: usize,
+}
+
+impl Relocatable {
+    pub fn new(segment_index: usize, offset: usize) -> Self {
+        Self {
+            segment_index,
+            offset,
+        }
+    }
+
+    fn encode(&self) -> u64 {
+        ((self.segment_index as u64) << OFFSET_BIT_LEN) + self.offset as u64
+    }
+
+    fn decode(value: u64) -> Self {
+        Self {
+            segment_index: (value >> OFFSET_BIT_LEN) as usize,
+            offset: (value & ((1 << OFFSET_BIT_LEN) - 1)) as usize,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryValue {
+    Int(Felt),
+    Relocatable(Relocatable),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PieMemory(pub Vec<(Relocatable, MemoryValue)>);
+
+impl PieMemory {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, AtlanticSdkError> {
+        // `usize::is_multiple_of` needs Rust 1.87.
+        #[allow(clippy::manual_is_multiple_of)]
+        if bytes.len() % CELL_BYTE_LEN != 0 {
+            return Err(AtlanticSdkError::InvalidPie(format!(
+                "memory.bin length {} is not a multiple of {}",
+                bytes.len(),
+                CELL_BYTE_LEN
+            )));
+        }
+        bytes
+            .chunks_exact(CELL_BYTE_LEN)
+            .map(|cell| {
+                let (address, value) = cell.split_at(ADDR_BYTE_LEN);
+                let address = u64::from_le_bytes(address.try_into().expect("8 byte address"));
+                let address = Relocatable::decode(address & !ADDR_BASE);
+                let value: [u8; FIELD_BYTE_LEN] = value.try_into().expect("32 byte value");
+                let value = if value[FIELD_BYTE_LEN - 1] & RELOCATABLE_FLAG != 0 {
+                    let encoded = u64::from_le_bytes(value[..8].try_into().expect("8 bytes"));
+                    MemoryValue::Relocatable(Relocatable::decode(encoded))
+                } else {
+                    MemoryValue::Int(Felt::from_bytes_le(&value)?)
+                };
+                Ok((address, value))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.0.len() * CELL_BYTE_LEN);
+        for (address, value) in &self.0 {
+            bytes.extend_from_slice(&(ADDR_BASE | address.encode()).to_le_bytes());
+            match value {
+                MemoryValue::Int(felt) => bytes.extend_from_slice(&felt.to_bytes_le()),
+                MemoryValue::Relocatable(relocatable) => {
+                    let mut value = [0u8; FIELD_BYTE_LEN];
+                    value[..8].copy_from_slice(&relocatable.encode().to_le_bytes());
+                    value[FIELD_BYTE_LEN - 1] = RELOCATABLE_FLAG;
+                    bytes.extend_from_slice(&value);
+                }
+            }
+        }
+        bytes
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SegmentInfo {
+    pub index: usize,
+    pub size: usize,
+}
+
+// Program as stored in a PIE: only the bytecode, builtins and entrypoint are kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrippedProgram {
+    pub data: Vec<Felt>,
+    pub builtins: Vec<String>,
+    pub main: usize,
+}
+
+// Felts are written as plain JSON numbers, which do not fit in any primitive integer.
+#[derive(Serialize, Deserialize)]
+struct RawStrippedProgram {
+    prime: Box<RawValue>,
+    data: Vec<Box<RawValue>>,
+    builtins: Vec<String>,
+    main: usize,
+}
+
+impl Serialize for StrippedProgram {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let number = |s: String| RawValue::from_string(s).map_err(serde::ser::Error::custom);
+        RawStrippedProgram {
+            prime: number(PRIME.to_string())?,
+            data: self
+                .data
+                .iter()
+                .map(|felt| number(felt.to_decimal_string()))
+                .collect::<Result<_, _>>()?,
+            builtins: self.builtins.clone(),
+            main: self.main,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StrippedProgram {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawStrippedProgram::deserialize(deserializer)?;
+        if raw.prime.get() != PRIME {
+            return Err(serde::de::Error::custom(format!(
+                "unsupported prime {}",
+                raw.prime.get()
+            )));
+        }
+        Ok(Self {
+            data: raw
+                .data
+                .iter()
+                .map(|value| value.get().trim_matches('"').parse())
+                .collect::<Result<_, _>>()
+                .map_err(serde::de::Error::custom)?,
+            builtins: raw.builtins,
+            main: raw.main,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PieMetadata {
+    pub program: StrippedProgram,
+    pub program_segment: SegmentInfo,
+    pub execution_segment: SegmentInfo,
+    pub ret_fp_segment: SegmentInfo,
+    pub ret_pc_segment: SegmentInfo,
+    pub builtin_segments: BTreeMap<String, SegmentInfo>,
+    #[serde(default)]
+    pub extra_segments: Vec<SegmentInfo>,
+}
+
+impl PieMetadata {
+    fn segments(&self) -> impl Iterator<Item = &SegmentInfo> {
+        [
+            &self.program_segment,
+            &self.execution_segment,
+            &self.ret_fp_segment,
+            &self.ret_pc_segment,
+        ]
+        .into_iter()
+        .chain(self.builtin_segments.values())
+        .chain(self.extra_segments.iter())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecutionResources {
+    pub n_steps: usize,
+    pub builtin_instance_counter: BTreeMap<String, usize>,
+    pub n_memory_holes: usize,
+}
+
+// Assembles a `pie.zip` accepted by `proof_generation` from the parts of a cairo-vm run.
+#[derive(Debug, Clone)]
+pub struct PieBuilder {
+    metadata: PieMetadata,
+    memory: PieMemory,
+    execution_resources: ExecutionResources,
+    additional_data: BTreeMap<String, serde_json::Value>,
+}
+
+impl PieBuilder {
+    pub fn new(
+        metadata: PieMetadata,
+        memory: PieMemory,
+        execution_resources: ExecutionResources,
+    ) -> Self {
+        Self {
+            metadata,
+            memory,
+            execution_resources,
+            additional_data: BTreeMap::new(),
+        }
+    }
+
+    // Builtin data keyed like `output_builtin`. Builtins left unset get empty data.
+    pub fn with_additional_data(mut self, builtin: &str, data: serde_json::Value) -> Self {
+        self.additional_data.insert(builtin.to_string(), data);
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), AtlanticSdkError> {
+        let invalid = |reason: String| Err(AtlanticSdkError::InvalidPie(reason));
+        let program = &self.metadata.program;
+        for builtin in &program.builtins {
+            if !self.metadata.builtin_segments.contains_key(builtin) {
+                return invalid(format!("missing segment for builtin {}", builtin));
+            }
+        }
+        for builtin in self.execution_resources.builtin_instance_counter.keys() {
+            let known = builtin
+                .strip_suffix("_builtin")
+                .is_some_and(|name| program.builtins.iter().any(|b| b == name));
+            if !known {
+                return invalid(format!("instance counter for unknown builtin {}", builtin));
+            }
+        }
+        if program.main >= program.data.len() {
+            return invalid(format!(
+                "main entrypoint {} is outside the program",
+                program.main
+            ));
+        }
+
+        let mut sizes = BTreeMap::new();
+        for segment in self.metadata.segments() {
+            if sizes.insert(segment.index, segment.size).is_some() {
+                return invalid(format!("segment {} is declared twice", segment.index));
+            }
+        }
+        let mut addresses = BTreeSet::new();
+        for (address, value) in &self.memory.0 {
+            match sizes.get(&address.segment_index) {
+                Some(size) if address.offset < *size => {}
+                _ => {
+                    return invalid(format!(
+                        "memory address {}:{} is outside its segment",
+                        address.segment_index, address.offset
+                    ))
+                }
+            }
+            if !addresses.insert(*address) {
+                return invalid(format!(
+                    "memory address {}:{} is written twice",
+                    address.segment_index, address.offset
+                ));
+            }
+            if let MemoryValue::Relocatable(value) = value {
+                if !sizes.contains_key(&value.segment_index) {
+                    return invalid(format!("unknown segment {} in memory", value.segment_index));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn build(&self) -> Result<Vec<u8>, AtlanticSdkError> {
+        self.validate()?;
+        let mut additional_data = serde_json::Map::new();
+        for builtin in &self.metadata.program.builtins {
+            let name = format!("{}_builtin", builtin);
+            let data = self
+                .additional_data
+                .get(&name)
+                .cloned()
+                .unwrap_or_else(|| default_additional_data(builtin));
+            additional_data.insert(name, data);
+        }
+
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        let files = [
+            ("metadata.json", serde_json::to_vec(&self.metadata)?),
+            ("memory.bin", self.memory.to_bytes()),
+            (
+                "additional_data.json",
+                serde_json::to_vec(&additional_data)?,
+            ),
+            (
+                "execution_resources.json",
+                serde_json::to_vec(&self.execution_resources)?,
+            ),
+            (
+                "version.json",
+                serde_json::to_vec(&serde_json::json!({ "cairo_pie": PIE_VERSION }))?,
+            ),
+        ];
+        for (name, content) in files {
+            writer.start_file(name, options).map_err(zip_error)?;
+            writer.write_all(&content)?;
+        }
+        Ok(writer.finish().map_err(zip_error)?.into_inner())
+    }
+}
+
+fn default_additional_data(builtin: &str) -> serde_json::Value {
+    match builtin {
+        "output" => serde_json::json!({ "pages": {}, "attributes": {} }),
+        "pedersen" => serde_json::json!([]),
+        _ => serde_json::Value::Null,
+    }
+}
+
+fn zip_error(err: zip::result::ZipError) -> AtlanticSdkError {
+    AtlanticSdkError::InvalidPie(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use zip::ZipArchive;
+
+    use super::*;
+
+    fn read_entries(archive: &[u8]) -> BTreeMap<String, Vec<u8>> {
+        let mut zip = ZipArchive::new(Cursor::new(archive)).unwrap();
+        (0..zip.len())
+            .map(|i| {
+                let mut entry = zip.by_index(i).unwrap();
+                let mut content = Vec::new();
+                entry.read_to_end(&mut content).unwrap();
+                (entry.name().to_string(), content)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rebuild_example_pie() {
+        let original = read_entries(&std::fs::read("examples/pie.zip").unwrap());
+        let metadata: PieMetadata = serde_json::from_slice(&original["metadata.json"]).unwrap();
+        let memory = PieMemory::from_bytes(&original["memory.bin"]).unwrap();
+        let execution_resources: ExecutionResources =
+            serde_json::from_slice(&original["execution_resources.json"]).unwrap();
+        assert_eq!(memory.to_bytes(), original["memory.bin"]);
+
+        let rebuilt = read_entries(
+            &PieBuilder::new(metadata.clone(), memory, execution_resources)
+                .build()
+                .unwrap(),
+        );
+        assert_eq!(
+            rebuilt.keys().collect::<Vec<_>>(),
+            original.keys().collect::<Vec<_>>()
+        );
+        assert_eq!(rebuilt["memory.bin"], original["memory.bin"]);
+        for name in ["additional_data.json", "version.json"] {
+            let rebuilt: serde_json::Value = serde_json::from_slice(&rebuilt[name]).unwrap();
+            let original: serde_json::Value = serde_json::from_slice(&original[name]).unwrap();
+            assert_eq!(rebuilt, original);
+        }
+        let reparsed: PieMetadata = serde_json::from_slice(&rebuilt["metadata.json"]).unwrap();
+        assert_eq!(reparsed, metadata);
+    }
+
+    #[test]
+    fn test_validate_memory_bounds() {
+        let original = read_entries(&std::fs::read("examples/pie.zip").unwrap());
+        let metadata: PieMetadata = serde_json::from_slice(&original["metadata.json"]).unwrap();
+        let memory = PieMemory(vec![(
+            Relocatable::new(0, metadata.program_segment.size),
+            MemoryValue::Int(Felt::from(1)),
+        )]);
+        let builder = PieBuilder::new(metadata, memory, ExecutionResources::default());
+        assert!(matches!(
+            builder.validate(),
+            Err(AtlanticSdkError::InvalidPie(_))
+        ));
+    }
+}
@@ -0,0 +1,292 @@
+use std::collections::BTreeMap;
+
+use serde::{Serialize, Serializer};
+use serde_json::value::RawValue;
+
+use crate::{error::AtlanticSdkError, felt::Felt};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputValue {
+    Felt(Felt),
+    Array(Vec<InputValue>),
+    Struct(ProgramInput),
+}
+
+impl From<Felt> for InputValue {
+    fn from(value: Felt) -> Self {
+        InputValue::Felt(value)
+    }
+}
+
+impl From<u64> for InputValue {
+    fn from(value: u64) -> Self {
+        InputValue::Felt(value.into())
+    }
+}
+
+impl From<ProgramInput> for InputValue {
+    fn from(value: ProgramInput) -> Self {
+        InputValue::Struct(value)
+    }
+}
+
+impl<T: Into<InputValue>> From<Vec<T>> for InputValue {
+    fn from(values: Vec<T>) -> Self {
+        InputValue::Array(values.into_iter().map(Into::into).collect())
+    }
+}
+
+// Felts are written as JSON numbers so hints read them as integers of any size.
+impl Serialize for InputValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            InputValue::Felt(felt) => RawValue::from_string(felt.to_decimal_string())
+                .map_err(serde::ser::Error::custom)?
+                .serialize(serializer),
+            InputValue::Array(values) => values.serialize(serializer),
+            InputValue::Struct(input) => input.serialize(serializer),
+        }
+    }
+}
+
+impl InputValue {
+    fn from_raw(raw: &RawValue) -> Result<Self, AtlanticSdkError> {
+        let text = raw.get().trim();
+        if text.starts_with('[') {
+            let values: Vec<Box<RawValue>> = serde_json::from_str(text)?;
+            values
+                .iter()
+                .map(|value| Self::from_raw(value))
+                .collect::<Result<_, _>>()
+                .map(InputValue::Array)
+        } else if text.starts_with('{') {
+            ProgramInput::from_json(text.as_bytes()).map(InputValue::Struct)
+        } else {
+            // Numbers are decimal, strings may be hex or decimal.
+            let value: String = if text.starts_with('"') {
+                serde_json::from_str(text)?
+            } else {
+                text.to_string()
+            };
+            Ok(InputValue::Felt(value.parse()?))
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            InputValue::Felt(_) => "felt",
+            InputValue::Array(_) => "array",
+            InputValue::Struct(_) => "struct",
+        }
+    }
+}
+
+// Typed contents of an `input_file`: a JSON object read by the program's hints, or matched
+// against the arguments of `main`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct ProgramInput(BTreeMap<String, InputValue>);
+
+impl ProgramInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn value(mut self, name: &str, value: impl Into<InputValue>) -> Self {
+        self.0.insert(name.to_string(), value.into());
+        self
+    }
+
+    pub fn felt(self, name: &str, value: impl Into<Felt>) -> Self {
+        self.value(name, value.into())
+    }
+
+    // Accepts `0x`-prefixed hex or decimal.
+    pub fn felt_str(self, name: &str, value: &str) -> Result<Self, AtlanticSdkError> {
+        Ok(self.value(name, value.parse::<Felt>()?))
+    }
+
+    pub fn array<T: Into<InputValue>>(
+        self,
+        name: &str,
+        values: impl IntoIterator<Item = T>,
+    ) -> Self {
+        self.value(
+            name,
+            InputValue::Array(values.into_iter().map(Into::into).collect()),
+        )
+    }
+
+    pub fn structure(self, name: &str, value: ProgramInput) -> Self {
+        self.value(name, value)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&InputValue> {
+        self.0.get(name)
+    }
+
+    pub fn from_json(input_file: &[u8]) -> Result<Self, AtlanticSdkError> {
+        let raw: BTreeMap<String, Box<RawValue>> = serde_json::from_slice(input_file)?;
+        raw.into_iter()
+            .map(|(name, value)| Ok((name, InputValue::from_raw(&value)?)))
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    pub fn to_json(&self) -> Result<Vec<u8>, AtlanticSdkError> {
+        Ok(serde_json::to_vec(self)?)
+    }
+
+    // Checks the input against the explicit arguments of `main` in a compiled Cairo 0
+    // program. Inputs for other entries are left alone, as hints may read any key.
+    pub fn validate(&self, program_file: &[u8]) -> Result<(), AtlanticSdkError> {
+        let program: serde_json::Value = serde_json::from_slice(program_file)?;
+        let identifiers = program
+            .get("identifiers")
+            .and_then(|identifiers| identifiers.as_object())
+            .ok_or_else(|| {
+                AtlanticSdkError::InvalidProgramInput(
+                    "program has no Cairo 0 identifiers to validate against".to_string(),
+                )
+            })?;
+        check_struct(self, "__main__.main.Args", identifiers, "main")
+    }
+}
+
+fn check_struct(
+    input: &ProgramInput,
+    struct_name: &str,
+    identifiers: &serde_json::Map<String, serde_json::Value>,
+    path: &str,
+) -> Result<(), AtlanticSdkError> {
+    let definition = resolve(struct_name, identifiers)
+        .filter(|definition| definition["type"] == "struct")
+        .ok_or_else(|| {
+            AtlanticSdkError::InvalidProgramInput(format!("unknown struct {}", struct_name))
+        })?;
+    let members = definition["members"]
+        .as_object()
+        .cloned()
+        .unwrap_or_default();
+    for (member, info) in members {
+        let member_path = format!("{}.{}", path, member);
+        let value = input.get(&member).ok_or_else(|| {
+            AtlanticSdkError::InvalidProgramInput(format!("missing {}", member_path))
+        })?;
+        let cairo_type = info["cairo_type"].as_str().unwrap_or_default();
+        check_value(value, cairo_type, identifiers, &member_path)?;
+    }
+    Ok(())
+}
+
+fn check_value(
+    value: &InputValue,
+    cairo_type: &str,
+    identifiers: &serde_json::Map<String, serde_json::Value>,
+    path: &str,
+) -> Result<(), AtlanticSdkError> {
+    let mismatch = |expected: &str| {
+        Err(AtlanticSdkError::InvalidProgramInput(format!(
+            "{} should be {} ({}), got {}",
+            path,
+            expected,
+            cairo_type,
+            value.kind()
+        )))
+    };
+    if let Some(element_type) = cairo_type.strip_suffix('*') {
+        let InputValue::Array(values) = value else {
+            return mismatch("an array");
+        };
+        return values.iter().enumerate().try_for_each(|(i, value)| {
+            check_value(
+                value,
+                element_type,
+                identifiers,
+                &format!("{}[{}]", path, i),
+            )
+        });
+    }
+    match (cairo_type, value) {
+        ("felt", InputValue::Felt(_)) => Ok(()),
+        ("felt", _) => mismatch("a felt"),
+        // Tuples and other anonymous types are not described in the identifiers.
+        (t, _) if t.starts_with('(') => Ok(()),
+        (_, InputValue::Struct(input)) => check_struct(input, cairo_type, identifiers, path),
+        _ => mismatch("a struct"),
+    }
+}
+
+fn resolve<'a>(
+    name: &str,
+    identifiers: &'a serde_json::Map<String, serde_json::Value>,
+) -> Option<&'a serde_json::Value> {
+    let mut name = name;
+    // Bounded to guard against cyclic aliases.
+    for _ in 0..16 {
+        let identifier = identifiers.get(name)?;
+        match identifier["type"].as_str() {
+            Some("alias") => name = identifier["destination"].as_str()?,
+            _ => return Some(identifier),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_program_input_json() {
+        let input = ProgramInput::new()
+            .felt("n", 3u64)
+            .array("arr", vec![9u64, 11, 5])
+            .felt_str(
+                "hash",
+                "0x193641eb151b0f41674641089952e60bc3aded26e3cf42793655c562b8c3aa0",
+            )
+            .unwrap()
+            .structure("point", ProgramInput::new().felt("x", 1u64).felt("y", 2u64));
+        let json = input.to_json().unwrap();
+        assert_eq!(
+            String::from_utf8(json.clone()).unwrap(),
+            r#"{"arr":[9,11,5],"hash":712730369212649401599582154587278289724262079847619466250461974481240603296,"n":3,"point":{"x":1,"y":2}}"#
+        );
+        assert_eq!(ProgramInput::from_json(&json).unwrap(), input);
+        let hex = ProgramInput::from_json(br#"{"n": "0x3"}"#).unwrap();
+        assert_eq!(hex.get("n"), Some(&InputValue::Felt(3u64.into())));
+    }
+
+    #[test]
+    fn test_validate_against_main() {
+        let identifiers = serde_json::json!({
+            "identifiers": {
+                "__main__.main.Args": {
+                    "type": "struct",
+                    "members": {
+                        "arr": {"cairo_type": "felt*", "offset": 0},
+                        "point": {"cairo_type": "__main__.P", "offset": 1}
+                    }
+                },
+                "__main__.P": {"type": "alias", "destination": "__main__.Point"},
+                "__main__.Point": {
+                    "type": "struct",
+                    "members": {"x": {"cairo_type": "felt", "offset": 0}}
+                }
+            }
+        });
+        let program = serde_json::to_vec(&identifiers).unwrap();
+        let input = ProgramInput::new()
+            .array("arr", vec![1u64, 2])
+            .structure("point", ProgramInput::new().felt("x", 1u64));
+        assert!(input.validate(&program).is_ok());
+        let input = input.felt("arr", 1u64);
+        assert!(matches!(
+            input.validate(&program),
+            Err(AtlanticSdkError::InvalidProgramInput(_))
+        ));
+        let sum = std::fs::read("examples/compiled_sum.json").unwrap();
+        assert!(ProgramInput::new().validate(&sum).is_ok());
+    }
+}
@@ -0,0 +1,275 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{error::AtlanticSdkError, felt::Felt, models::AtlanticSdk};
+
+// proof.json produced by the Stone prover. Fields not modelled here are kept in `extra`, so
+// serializing a parsed proof gives back the same proof.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StoneProof {
+    pub proof_parameters: ProofParameters,
+    pub public_input: PublicInput,
+    #[serde(default)]
+    pub annotations: Vec<String>,
+    #[serde(default)]
+    pub extra_annotations: Vec<String>,
+    pub proof_hex: String,
+    // Prover settings are only informational for consumers of the proof.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prover_config: Option<serde_json::Value>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProofParameters {
+    pub stark: StarkParameters,
+    pub use_extension_field: bool,
+    pub channel_hash: Option<String>,
+    pub commitment_hash: Option<String>,
+    pub pow_hash: Option<String>,
+    pub n_verifier_friendly_commitment_layers: Option<u32>,
+    pub statement: Option<serde_json::Value>,
+    // e.g. the verifier_friendly_* settings of newer Stone versions.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StarkParameters {
+    pub fri: FriParameters,
+    pub log_n_cosets: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FriParameters {
+    pub fri_step_list: Vec<u32>,
+    pub last_layer_degree_bound: u32,
+    pub n_queries: u32,
+    pub proof_of_work_bits: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PublicInput {
+    pub layout: String,
+    pub n_steps: u64,
+    pub rc_min: u64,
+    pub rc_max: u64,
+    pub memory_segments: BTreeMap<String, MemorySegment>,
+    pub public_memory: Vec<PublicMemoryCell>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dynamic_params: Option<BTreeMap<String, u64>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemorySegment {
+    pub begin_addr: u64,
+    pub stop_ptr: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PublicMemoryCell {
+    pub address: u64,
+    pub page: u64,
+    pub value: Felt,
+}
+
+// A task run by the bootloader, as laid out in the bootloader output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BootloadedTask {
+    pub program_hash: Felt,
+    pub output: Vec<Felt>,
+}
+
+impl FromStr for StoneProof {
+    type Err = AtlanticSdkError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(serde_json::from_str(s)?)
+    }
+}
+
+impl StoneProof {
+    pub fn layout(&self) -> &str {
+        &self.public_input.layout
+    }
+
+    pub fn n_steps(&self) -> u64 {
+        self.public_input.n_steps
+    }
+
+    pub fn segment(&self, name: &str) -> Option<MemorySegment> {
+        self.public_input.memory_segments.get(name).copied()
+    }
+
+    // Values of the output segment, read from the public memory.
+    pub fn program_output(&self) -> Result<Vec<Felt>, AtlanticSdkError> {
+        let output = self
+            .segment("output")
+            .ok_or_else(|| AtlanticSdkError::InvalidProof("missing output segment".to_string()))?;
+        let cells: BTreeMap<u64, Felt> = self
+            .public_input
+            .public_memory
+            .iter()
+            .filter(|cell| cell.address >= output.begin_addr && cell.address < output.stop_ptr)
+            .map(|cell| (cell.address, cell.value))
+            .collect();
+        (output.begin_addr..output.stop_ptr)
+            .map(|address| {
+                cells.get(&address).copied().ok_or_else(|| {
+                    AtlanticSdkError::InvalidProof(format!(
+                        "output cell {} is not in public memory",
+                        address
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    // Atlantic proves programs through the bootloader, whose output is
+    // `[n_tasks, (task_output_size, program_hash, task_output...)...]`.
+    pub fn bootloaded_tasks(&self) -> Result<Vec<BootloadedTask>, AtlanticSdkError> {
+        let output = self.program_output()?;
+        let invalid = || AtlanticSdkError::InvalidProof("malformed bootloader output".to_string());
+        let mut values = output.iter();
+        let n_tasks = parse_usize(values.next().ok_or_else(invalid)?).ok_or_else(invalid)?;
+        // Every task takes at least two values, which bounds a corrupt `n_tasks`.
+        let mut tasks = Vec::with_capacity(n_tasks.min(output.len() / 2));
+        for _ in 0..n_tasks {
+            let size = parse_usize(values.next().ok_or_else(invalid)?).ok_or_else(invalid)?;
+            if size < 2 {
+                return Err(invalid());
+            }
+            let program_hash = *values.next().ok_or_else(invalid)?;
+            let task_output: Vec<Felt> = values.by_ref().take(size - 2).copied().collect();
+            if task_output.len() != size - 2 {
+                return Err(invalid());
+            }
+            tasks.push(BootloadedTask {
+                program_hash,
+                output: task_output,
+            });
+        }
+        Ok(tasks)
+    }
+
+    // Hash of the program proven through the bootloader; the first task when there are several.
+    pub fn program_hash(&self) -> Result<Felt, AtlanticSdkError> {
+        self.bootloaded_tasks()?
+            .into_iter()
+            .next()
+            .map(|task| task.program_hash)
+            .ok_or_else(|| AtlanticSdkError::InvalidProof("bootloader ran no tasks".to_string()))
+    }
+}
+
+fn parse_usize(value: &Felt) -> Option<usize> {
+    value.to_u64().and_then(|value| usize::try_from(value).ok())
+}
+
+impl AtlanticSdk {
+    pub async fn get_proof_typed(&self, query_id: String) -> Result<StoneProof, AtlanticSdkError> {
+        self.get_proof(query_id).await?.parse()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) const PROOF: &str = r#"{
+        "proof_parameters": {
+            "stark": {
+                "fri": {
+                    "fri_step_list": [0, 4, 4, 3],
+                    "last_layer_degree_bound": 128,
+                    "n_queries": 10,
+                    "proof_of_work_bits": 30
+                },
+                "log_n_cosets": 2
+            },
+            "use_extension_field": false,
+            "channel_hash": "poseidon3",
+            "commitment_hash": "keccak256_masked160_lsb",
+            "n_verifier_friendly_commitment_layers": 9999,
+            "pow_hash": "keccak256",
+            "statement": {"page_hash": "pedersen"}
+        },
+        "annotations": [],
+        "proof_hex": "0x0123",
+        "public_input": {
+            "layout": "recursive_with_poseidon",
+            "n_steps": 16384,
+            "rc_min": 32762,
+            "rc_max": 32769,
+            "memory_segments": {
+                "program": {"begin_addr": 1, "stop_ptr": 5},
+                "execution": {"begin_addr": 40, "stop_ptr": 1000},
+                "output": {"begin_addr": 1000, "stop_ptr": 1005}
+            },
+            "public_memory": [
+                {"address": 1, "page": 0, "value": "0x40780017fff7fff"},
+                {"address": 1000, "page": 0, "value": "0x1"},
+                {"address": 1001, "page": 0, "value": "0x4"},
+                {"address": 1002, "page": 0, "value": "0x193641eb151b0f41674641089952e60bc3aded26e3cf42793655c562b8c3aa0"},
+                {"address": 1003, "page": 0, "value": "0x5ab580b04e3532b6b18f81cfa654a05e29dd8e2352d88df1e765a84072db07"},
+                {"address": 1004, "page": 0, "value": "0x74f69aa694b52f25bac8892c49500deffd5efa54b9c067ce067f0ff81de21fc"}
+            ],
+            "dynamic_params": null
+        }
+    }"#;
+
+    #[test]
+    fn test_parse_proof() {
+        let proof: StoneProof = PROOF.parse().unwrap();
+        assert_eq!(proof.layout(), "recursive_with_poseidon");
+        assert_eq!(proof.n_steps(), 16384);
+        assert_eq!(proof.program_output().unwrap().len(), 5);
+        assert_eq!(
+            proof.program_hash().unwrap().to_string(),
+            "0x193641eb151b0f41674641089952e60bc3aded26e3cf42793655c562b8c3aa0"
+        );
+        let tasks = proof.bootloaded_tasks().unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].output.len(), 2);
+    }
+
+    #[test]
+    fn test_keeps_unknown_fields() {
+        let proof_json = PROOF
+            .replace(
+                r#""use_extension_field": false,"#,
+                r#""use_extension_field": false, "verifier_friendly_channel_updates": true,"#,
+            )
+            .replace(
+                r#""annotations": [],"#,
+                r#""annotations": [], "prover_version": "stone6","#,
+            );
+        let proof: StoneProof = proof_json.parse().unwrap();
+        let value = serde_json::to_value(&proof).unwrap();
+        assert_eq!(
+            value["proof_parameters"]["verifier_friendly_channel_updates"],
+            true
+        );
+        assert_eq!(value["prover_version"], "stone6");
+        let reparsed: StoneProof = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&reparsed).unwrap(), value);
+    }
+
+    #[test]
+    fn test_oversized_task_count() {
+        let proof: StoneProof = PROOF
+            .replace(
+                r#""address": 1000, "page": 0, "value": "0x1""#,
+                r#""address": 1000, "page": 0, "value": "0xffffffffffffffff""#,
+            )
+            .parse()
+            .unwrap();
+        assert!(matches!(
+            proof.bootloaded_tasks(),
+            Err(AtlanticSdkError::InvalidProof(_))
+        ));
+    }
+}
@@ -1,106 +1,78 @@
-use reqwest::multipart;
-
 use crate::{
+    atlantic_query::{FilePart, SubmitForm},
     error::AtlanticSdkError,
-    models::{Layout, ProverVersion, QueryResponse},
+    models::{CairoVersion, Layout, ProverVersion, QueryResponse},
 };
 
 use super::AtlanticSdk;
+
+// Where `prove_program` generates the trace of a program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TraceSource {
+    #[default]
+    Remote,
+    // Runs the program in cairo-vm and only pays for proof generation. Without the
+    // `local_trace` feature, `prove_program` fails with `FeatureNotEnabled`.
+    Local,
+}
+
 impl AtlanticSdk {
     pub async fn trace_generation(
         &self,
         program_hash: &str,
         program_file: Vec<u8>,
         input_file: Vec<u8>,
+        cairo_version: CairoVersion,
         external_id: &str,
     ) -> Result<QueryResponse, AtlanticSdkError> {
-        let form = multipart::Form::new()
+        let form = SubmitForm::new()
             .text("programHash", program_hash.to_string())
-            .part(
-                "programFile",
-                multipart::Part::bytes(program_file)
-                    .file_name("program.json")
-                    .mime_str("application/json")?,
-            )
+            .part("programFile", cairo_version.program_part(program_file)?)
             .part(
                 "inputFile",
-                multipart::Part::bytes(input_file)
-                    .file_name("input.json")
-                    .mime_str("application/json")?,
+                FilePart::new(input_file, "input.json", "application/json"),
             )
-            .text("cairoVersion", 0.to_string())
+            .text("cairoVersion", cairo_version.to_string())
             .text("externalId", external_id.to_string());
 
-        let client = reqwest::Client::new();
-        let url = format!(
-            "{}?apiKey={}",
-            self.proof_generation_trace_generation.trace_generation, self.api_key
-        );
-        let response = client.post(&url).multipart(form).send().await?;
-
-        let status = response.status();
-
-        match status {
-            reqwest::StatusCode::CREATED => {
-                let response = response.json::<QueryResponse>().await?;
-                Ok(response)
-            }
-            _ => {
-                let response = response.text().await?;
-                Err(AtlanticSdkError::CustomError(response))
-            }
-        }
+        self.submit_query(
+            &self.proof_generation_trace_generation.trace_generation,
+            form,
+        )
+        .await
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn trace_gen_to_proof_gen(
         &self,
         program_hash: &str,
         program_file: Vec<u8>,
         input_file: Vec<u8>,
+        cairo_version: CairoVersion,
         layout: Layout,
         prover: ProverVersion,
         external_id: &str,
     ) -> Result<QueryResponse, AtlanticSdkError> {
-        let form = multipart::Form::new()
+        prover.validate_layout(&layout)?;
+        let form = SubmitForm::new()
             .text("programHash", program_hash.to_string())
-            .part(
-                "programFile",
-                multipart::Part::bytes(program_file)
-                    .file_name("program.json")
-                    .mime_str("application/json")?,
-            )
+            .part("programFile", cairo_version.program_part(program_file)?)
             .part(
                 "inputFile",
-                multipart::Part::bytes(input_file)
-                    .file_name("input.json")
-                    .mime_str("application/json")?,
+                FilePart::new(input_file, "input.json", "application/json"),
             )
-            .text("cairoVersion", 0.to_string())
+            .text("cairoVersion", cairo_version.to_string())
             .text("layout", layout.to_string())
             .text("prover", prover.to_string())
             .text("externalId", external_id.to_string());
 
-        let client = reqwest::Client::new();
-        let url = format!(
-            "{}?apiKey={}",
-            self.proof_generation_trace_generation
+        self.submit_query(
+            &self
+                .proof_generation_trace_generation
                 .trace_gen_to_proof_gen,
-            self.api_key
-        );
-        let response = client.post(&url).multipart(form).send().await?;
-
-        let status = response.status();
-
-        match status {
-            reqwest::StatusCode::CREATED => {
-                let response = response.json::<QueryResponse>().await?;
-                Ok(response)
-            }
-            _ => {
-                let response = response.text().await?;
-                Err(AtlanticSdkError::CustomError(response))
-            }
-        }
+            form,
+        )
+        .await
     }
     pub async fn proof_generation(
         &self,
@@ -109,35 +81,117 @@ impl AtlanticSdk {
         prover: ProverVersion,
         external_id: &str,
     ) -> Result<QueryResponse, AtlanticSdkError> {
-        let form = multipart::Form::new()
+        prover.validate_layout(&layout)?;
+        let form = SubmitForm::new()
             .part(
                 "pieFile",
-                multipart::Part::bytes(pie_file)
-                    .file_name("pie.zip")
-                    .mime_str("application/zip")?,
+                FilePart::new(pie_file, "pie.zip", "application/zip"),
             )
             .text("layout", layout.to_string())
             .text("prover", prover.to_string())
             .text("externalId", external_id.to_string());
 
-        let client = reqwest::Client::new();
-        let url = format!(
-            "{}?apiKey={}",
-            self.proof_generation_trace_generation.proof_generation, self.api_key
-        );
-        let response = client.post(&url).multipart(form).send().await?;
-
-        let status = response.status();
+        self.submit_query(
+            &self.proof_generation_trace_generation.proof_generation,
+            form,
+        )
+        .await
+    }
 
-        match status {
-            reqwest::StatusCode::CREATED => {
-                let response = response.json::<QueryResponse>().await?;
-                Ok(response)
+    // Proves a program from its source files, generating the trace with Atlantic or locally.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn prove_program(
+        &self,
+        program_hash: &str,
+        program_file: Vec<u8>,
+        input_file: Vec<u8>,
+        cairo_version: CairoVersion,
+        layout: Layout,
+        prover: ProverVersion,
+        trace_source: TraceSource,
+        external_id: &str,
+    ) -> Result<QueryResponse, AtlanticSdkError> {
+        match trace_source {
+            TraceSource::Remote => {
+                self.trace_gen_to_proof_gen(
+                    program_hash,
+                    program_file,
+                    input_file,
+                    cairo_version,
+                    layout,
+                    prover,
+                    external_id,
+                )
+                .await
             }
-            _ => {
-                let response = response.text().await?;
-                Err(AtlanticSdkError::CustomError(response))
+            #[cfg(not(feature = "local_trace"))]
+            TraceSource::Local => Err(AtlanticSdkError::FeatureNotEnabled("local_trace")),
+            #[cfg(feature = "local_trace")]
+            TraceSource::Local => {
+                if cairo_version != CairoVersion::Zero {
+                    return Err(AtlanticSdkError::TraceGenerationFailed(
+                        "only Cairo 0 programs can be run locally".to_string(),
+                    ));
+                }
+                let generator = crate::local_trace::LocalTraceGenerator::new(layout);
+                self.prove_program_locally(
+                    generator,
+                    program_file,
+                    input_file,
+                    layout,
+                    prover,
+                    external_id,
+                )
+                .await
             }
         }
     }
+
+    // Like `prove_program` with `TraceSource::Local`, for programs whose hints have to be
+    // registered on the generator.
+    #[cfg(feature = "local_trace")]
+    pub async fn prove_program_locally(
+        &self,
+        generator: crate::local_trace::LocalTraceGenerator,
+        program_file: Vec<u8>,
+        input_file: Vec<u8>,
+        layout: Layout,
+        prover: ProverVersion,
+        external_id: &str,
+    ) -> Result<QueryResponse, AtlanticSdkError> {
+        prover.validate_layout(&layout)?;
+        let pie =
+            tokio::task::spawn_blocking(move || generator.generate_pie(&program_file, &input_file))
+                .await
+                .map_err(|err| AtlanticSdkError::TraceGenerationFailed(err.to_string()))??;
+        self.proof_generation(pie, layout, prover, external_id)
+            .await
+    }
+}
+
+#[cfg(all(test, not(feature = "local_trace")))]
+mod tests {
+    use super::*;
+    use crate::mock_server;
+
+    #[tokio::test]
+    async fn test_local_trace_needs_feature() {
+        let sdk = AtlanticSdk::new("api_key".to_string(), mock_server::closed_url().await).unwrap();
+        let result = sdk
+            .prove_program(
+                "0x1",
+                b"{}".to_vec(),
+                b"{}".to_vec(),
+                CairoVersion::Zero,
+                Layout::Recursive,
+                ProverVersion::Starkware,
+                TraceSource::Local,
+                "",
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(AtlanticSdkError::FeatureNotEnabled("local_trace"))
+        ));
+    }
 }
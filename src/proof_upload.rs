@@ -0,0 +1,136 @@
+use std::io::{Cursor, Read, Write};
+
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::{atlantic_query::FilePart, error::AtlanticSdkError, proof::StoneProof};
+
+// Name of the proof inside the archive uploaded to the proof verification endpoint.
+pub const PROOF_ENTRY: &str = "proof.json";
+// Largest `proof.json` read back out of an archive.
+const MAX_PROOF_JSON_LEN: u64 = 512 * 1024 * 1024;
+
+// A validated `proofFile` for `l2_proof_verification_upload`: either a zip archive holding a
+// single `proof.json`, or the proof JSON uploaded as is. The API documents neither layout, so
+// this is opt-in; `l2_proof_verification` keeps uploading the caller's bytes unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProofUpload {
+    Zip(Vec<u8>),
+    Json(Vec<u8>),
+}
+
+impl ProofUpload {
+    // Packages proof JSON, e.g. the output of `get_proof`, into a zip archive.
+    pub fn zip_json(proof_json: &[u8]) -> Result<Self, AtlanticSdkError> {
+        parse_proof(proof_json)?;
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        writer.start_file(PROOF_ENTRY, options).map_err(zip_error)?;
+        writer.write_all(proof_json)?;
+        let archive = writer.finish().map_err(zip_error)?.into_inner();
+        Ok(Self::Zip(archive))
+    }
+
+    // Re-serializes `proof`; prefer `zip_json` when the original bytes are at hand.
+    pub fn zip_proof(proof: &StoneProof) -> Result<Self, AtlanticSdkError> {
+        Self::zip_json(&serde_json::to_vec(proof)?)
+    }
+
+    pub fn from_json(proof_json: Vec<u8>) -> Result<Self, AtlanticSdkError> {
+        parse_proof(&proof_json)?;
+        Ok(Self::Json(proof_json))
+    }
+
+    // Checks that an existing archive contains exactly one `proof.json` holding a Stone proof.
+    pub fn from_zip(archive: Vec<u8>) -> Result<Self, AtlanticSdkError> {
+        let mut zip = ZipArchive::new(Cursor::new(archive.as_slice())).map_err(zip_error)?;
+        if zip.len() != 1 {
+            return Err(AtlanticSdkError::InvalidProof(format!(
+                "proof archive must contain only {}, found {} entries",
+                PROOF_ENTRY,
+                zip.len()
+            )));
+        }
+        let entry = zip.by_name(PROOF_ENTRY).map_err(zip_error)?;
+        let mut proof_json = Vec::new();
+        entry
+            .take(MAX_PROOF_JSON_LEN + 1)
+            .read_to_end(&mut proof_json)?;
+        if proof_json.len() as u64 > MAX_PROOF_JSON_LEN {
+            return Err(AtlanticSdkError::InvalidProof(format!(
+                "{} is larger than {} bytes",
+                PROOF_ENTRY, MAX_PROOF_JSON_LEN
+            )));
+        }
+        parse_proof(&proof_json)?;
+        drop(zip);
+        Ok(Self::Zip(archive))
+    }
+
+    pub(crate) fn into_part(self) -> FilePart {
+        match self {
+            ProofUpload::Zip(bytes) => FilePart::new(bytes, "proof.zip", "application/zip"),
+            ProofUpload::Json(bytes) => FilePart::new(bytes, PROOF_ENTRY, "application/json"),
+        }
+    }
+}
+
+fn parse_proof(proof_json: &[u8]) -> Result<StoneProof, AtlanticSdkError> {
+    serde_json::from_slice(proof_json)
+        .map_err(|err| AtlanticSdkError::InvalidProof(format!("invalid proof JSON: {}", err)))
+}
+
+fn zip_error(err: zip::result::ZipError) -> AtlanticSdkError {
+    AtlanticSdkError::InvalidProof(format!("invalid proof archive: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::{extract::Multipart, http::StatusCode, routing::post, Router};
+
+    use super::*;
+    use crate::{mock_server, models::ProverVersion, proof::tests::PROOF};
+
+    #[test]
+    fn test_zip_roundtrip() {
+        let ProofUpload::Zip(archive) = ProofUpload::zip_json(PROOF.as_bytes()).unwrap() else {
+            panic!("expected a zip upload");
+        };
+        assert!(ProofUpload::from_zip(archive).is_ok());
+        assert!(ProofUpload::from_zip(b"not a zip".to_vec()).is_err());
+        assert!(ProofUpload::from_json(b"{}".to_vec()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_l2_proof_verification_uploads_raw_bytes() {
+        let uploaded = Arc::new(Mutex::new(None));
+        let sink = uploaded.clone();
+        let sdk = mock_server::sdk(Router::new().route(
+            "/v1/l2/atlantic-query/proof-verification",
+            post(move |mut form: Multipart| async move {
+                while let Some(field) = form.next_field().await.unwrap() {
+                    if field.name() == Some("proofFile") {
+                        let file_name = field.file_name().unwrap().to_string();
+                        let bytes = field.bytes().await.unwrap().to_vec();
+                        *sink.lock().unwrap() = Some((file_name, bytes));
+                    }
+                }
+                (
+                    StatusCode::CREATED,
+                    r#"{"atlanticQueryId": "01JDKQF9VY2NDBFZAFNFXZC17Z"}"#,
+                )
+            }),
+        ))
+        .await;
+
+        // Not a zip, and still uploaded unchanged.
+        sdk.l2_proof_verification(b"proof".to_vec(), false, ProverVersion::Starkware, "")
+            .await
+            .unwrap();
+        assert_eq!(
+            uploaded.lock().unwrap().take(),
+            Some(("proof.zip".to_string(), b"proof".to_vec()))
+        );
+    }
+}
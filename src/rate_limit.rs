@@ -0,0 +1,157 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tracing::debug;
+
+use crate::models::AtlanticSdk;
+
+// `requests` may be sent per `per`, with bursts of up to `requests`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quota {
+    pub requests: u32,
+    pub per: Duration,
+}
+
+impl Quota {
+    pub fn per_second(requests: u32) -> Self {
+        Self {
+            requests,
+            per: Duration::from_secs(1),
+        }
+    }
+    pub fn per_minute(requests: u32) -> Self {
+        Self {
+            requests,
+            per: Duration::from_secs(60),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitConfig {
+    pub submit: Quota,
+    pub read: Quota,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            submit: Quota::per_second(2),
+            read: Quota::per_second(10),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndpointKind {
+    Submit,
+    Read,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(quota: Quota, now: Instant) -> Self {
+        let capacity = f64::from(quota.requests.max(1));
+        Self {
+            capacity,
+            tokens: capacity,
+            refill_per_sec: capacity / quota.per.as_secs_f64().max(f64::EPSILON),
+            last_refill: now,
+        }
+    }
+
+    // Takes a token, or returns how long to wait until one is available.
+    fn try_take(&mut self, now: Instant) -> Result<(), Duration> {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - self.tokens) / self.refill_per_sec,
+            ))
+        }
+    }
+}
+
+// Cloning shares the buckets, so every clone of an `AtlanticSdk` draws from the same budget.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    submit: Arc<Mutex<TokenBucket>>,
+    read: Arc<Mutex<TokenBucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        let now = Instant::now();
+        Self {
+            submit: Arc::new(Mutex::new(TokenBucket::new(config.submit, now))),
+            read: Arc::new(Mutex::new(TokenBucket::new(config.read, now))),
+        }
+    }
+
+    pub async fn acquire(&self, kind: EndpointKind) {
+        let bucket = match kind {
+            EndpointKind::Submit => &self.submit,
+            EndpointKind::Read => &self.read,
+        };
+        loop {
+            let wait = {
+                let mut bucket = bucket.lock().unwrap_or_else(|e| e.into_inner());
+                match bucket.try_take(Instant::now()) {
+                    Ok(()) => return,
+                    Err(wait) => wait,
+                }
+            };
+            debug!(?kind, ?wait, "Rate limit reached, waiting");
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+impl AtlanticSdk {
+    pub fn with_rate_limit(mut self, config: RateLimitConfig) -> Self {
+        self.rate_limiter = Some(RateLimiter::new(config));
+        self
+    }
+
+    pub(crate) async fn throttle(&self, kind: EndpointKind) {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire(kind).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_bucket() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(Quota::per_second(2), start);
+        assert!(bucket.try_take(start).is_ok());
+        assert!(bucket.try_take(start).is_ok());
+        let wait = bucket.try_take(start).unwrap_err();
+        assert_eq!(wait, Duration::from_millis(500));
+        assert!(bucket.try_take(start + Duration::from_millis(500)).is_ok());
+        // Idle time never refills past the burst size.
+        let later = start + Duration::from_secs(60);
+        assert!(bucket.try_take(later).is_ok());
+        assert!(bucket.try_take(later).is_ok());
+        assert!(bucket.try_take(later).is_err());
+    }
+}
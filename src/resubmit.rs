@@ -0,0 +1,308 @@
+use tracing::{info, warn};
+
+use crate::{
+    error::AtlanticSdkError,
+    models::{
+        AtlanticSdk, CairoVersion, Chain, JobResponse, Layout, ProverVersion, QueryResponse,
+        QueryStep, SharpQueryDetails,
+    },
+    proof_upload::ProofUpload,
+};
+
+// Artifact paths recorded in the job contexts of a query.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueryArtifacts {
+    pub pie_path: Option<String>,
+    pub proof_path: Option<String>,
+    pub program_path: Option<String>,
+    pub input_path: Option<String>,
+    pub layout: Option<Layout>,
+    pub cairo_version: Option<CairoVersion>,
+}
+
+impl QueryArtifacts {
+    pub fn from_jobs(jobs: &JobResponse) -> Self {
+        let mut artifacts = Self::default();
+        for context in jobs.jobs.iter().filter_map(|job| job.context.as_ref()) {
+            artifacts.pie_path = artifacts.pie_path.or(context.pie_path.clone());
+            artifacts.proof_path = artifacts.proof_path.or(context.proof_path.clone());
+            artifacts.program_path = artifacts.program_path.or(context.program_path.clone());
+            artifacts.input_path = artifacts.input_path.or(context.input_path.clone());
+            artifacts.layout = artifacts
+                .layout
+                .or(context.layout.as_deref().and_then(|l| l.parse().ok()));
+            artifacts.cairo_version = artifacts.cairo_version.or(context
+                .cairo_version
+                .and_then(|v| CairoVersion::try_from(v as u64).ok()));
+        }
+        artifacts
+    }
+}
+
+impl AtlanticSdk {
+    // Submits a new query that starts at `from_step`, reusing the artifacts of `sharp_query_id`.
+    // The new query keeps the original external id, or gets `resubmit:<sharp_query_id>` when
+    // there is none; with a ledger configured its entry also records `resubmitted_from`, see
+    // `Ledger::find_resubmissions`.
+    pub async fn resubmit(
+        &self,
+        sharp_query_id: &str,
+        from_step: QueryStep,
+    ) -> Result<QueryResponse, AtlanticSdkError> {
+        let response = self.submit_from_step(sharp_query_id, from_step).await?;
+        if let Some(ledger) = &self.ledger {
+            if let Err(err) = ledger
+                .mark_resubmitted_from(&response.atlantic_query_id, sharp_query_id)
+                .await
+            {
+                warn!(
+                    "Failed to link sharpQueryId {} to {} in ledger: {}",
+                    response.atlantic_query_id, sharp_query_id, err
+                );
+            }
+        }
+        Ok(response)
+    }
+
+    async fn submit_from_step(
+        &self,
+        sharp_query_id: &str,
+        from_step: QueryStep,
+    ) -> Result<QueryResponse, AtlanticSdkError> {
+        let query = self.get_sharp_query(sharp_query_id).await?.sharp_query;
+        let jobs = self.get_sharp_query_jobs(sharp_query_id).await?;
+        let artifacts = QueryArtifacts::from_jobs(&jobs);
+        let external_id = if query.external_id.is_empty() {
+            format!("resubmit:{}", sharp_query_id)
+        } else {
+            query.external_id.clone()
+        };
+        info!(
+            "Resubmitting sharpQueryId {} from step {}",
+            sharp_query_id, from_step
+        );
+
+        let chain = query.chain.unwrap_or(Chain::L2);
+        let prover = query.prover.unwrap_or(ProverVersion::Starkware);
+        let layout = artifacts.layout.or(query.layout).unwrap_or(Layout::Auto);
+        let mock_fact_hash = query.is_fact_mocked.unwrap_or(false);
+        let verifies = has_step(&query, QueryStep::ProofVerification);
+
+        match from_step {
+            QueryStep::TraceGeneration => {
+                let program_file = self
+                    .get_artifact(required(&artifacts.program_path, "programPath")?)
+                    .await?;
+                let input_file = self
+                    .get_artifact(required(&artifacts.input_path, "inputPath")?)
+                    .await?;
+                let cairo_version = artifacts.cairo_version.unwrap_or(CairoVersion::Zero);
+                let program_hash = query.program_hash.map(|hash| hash.to_string());
+                let required_hash = || {
+                    program_hash
+                        .as_deref()
+                        .ok_or_else(|| AtlanticSdkError::MissingProgramHash(sharp_query_id.into()))
+                };
+                if verifies {
+                    self.atlantic_query(
+                        chain,
+                        program_hash.as_deref(),
+                        program_file,
+                        input_file,
+                        cairo_version,
+                        prover,
+                        mock_fact_hash,
+                        &external_id,
+                    )
+                    .await
+                } else if has_step(&query, QueryStep::ProofGeneration) {
+                    self.trace_gen_to_proof_gen(
+                        required_hash()?,
+                        program_file,
+                        input_file,
+                        cairo_version,
+                        layout,
+                        prover,
+                        &external_id,
+                    )
+                    .await
+                } else {
+                    self.trace_generation(
+                        required_hash()?,
+                        program_file,
+                        input_file,
+                        cairo_version,
+                        &external_id,
+                    )
+                    .await
+                }
+            }
+            QueryStep::ProofGeneration => {
+                let pie_file = self
+                    .get_artifact(required(&artifacts.pie_path, "piePath")?)
+                    .await?;
+                if verifies {
+                    self.proof_generation_verification(
+                        chain,
+                        pie_file,
+                        layout,
+                        prover,
+                        mock_fact_hash,
+                        &external_id,
+                    )
+                    .await
+                } else {
+                    self.proof_generation(pie_file, layout, prover, &external_id)
+                        .await
+                }
+            }
+            // The proof already exists, so both steps resume from it on the L2 verification
+            // endpoint, which computes the fact hash before verifying.
+            QueryStep::FactHashGeneration | QueryStep::ProofVerification => {
+                if chain != Chain::L2 {
+                    return Err(AtlanticSdkError::CustomError(format!(
+                        "Resubmitting from {} is only available on L2",
+                        from_step
+                    )));
+                }
+                let proof_file = self
+                    .get_artifact(required(&artifacts.proof_path, "proofPath")?)
+                    .await?;
+                self.l2_proof_verification_upload(
+                    ProofUpload::zip_json(&proof_file)?,
+                    mock_fact_hash,
+                    prover,
+                    &external_id,
+                )
+                .await
+            }
+        }
+    }
+}
+
+fn has_step(query: &SharpQueryDetails, step: QueryStep) -> bool {
+    query.steps.iter().any(|s| *s == step.to_string())
+}
+
+fn required<'a>(path: &'a Option<String>, name: &str) -> Result<&'a str, AtlanticSdkError> {
+    path.as_deref()
+        .ok_or_else(|| AtlanticSdkError::MissingArtifact(name.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{http::StatusCode, routing::get, routing::post, Router};
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::{
+        ledger::Ledger,
+        mock_server,
+        models::tests::{SHARP_QUERY, SHARP_QUERY_JOBS},
+        proof::tests::PROOF,
+    };
+
+    const QUERY_ID: &str = "01JDKQF9VY2NDBFZAFNFXZC17Z";
+
+    async fn mock_sdk(query: Value, ledger: Ledger) -> AtlanticSdk {
+        let query = query.to_string();
+        let router = Router::new()
+            .route("/v1/atlantic-query/{id}", get(move || async move { query }))
+            .route(
+                "/v1/atlantic-query-jobs/{id}",
+                get(|| async { SHARP_QUERY_JOBS }),
+            )
+            .route(
+                "/sharp_queries/query_01JDKQF9VY2NDBFZAFNFXZC17Z/{artifact}",
+                get(|| async { PROOF }),
+            )
+            .route(
+                "/v1/l2/atlantic-query/proof-verification",
+                post(|| async {
+                    (
+                        StatusCode::CREATED,
+                        r#"{"atlanticQueryId": "01JDKRZ3WQ7Y1M8B6V1DNV4E0X"}"#,
+                    )
+                }),
+            );
+        let url = mock_server::serve(router).await;
+        let mut sdk = AtlanticSdk::new("api_key".to_string(), url.clone())
+            .unwrap()
+            .with_ledger(ledger);
+        sdk.artifacts_url = url;
+        sdk
+    }
+
+    fn query_with(update: impl FnOnce(&mut Value)) -> Value {
+        let mut query: Value = serde_json::from_str(SHARP_QUERY).unwrap();
+        update(&mut query["atlanticQuery"]);
+        query
+    }
+
+    #[test]
+    fn test_artifacts_from_jobs() {
+        let jobs: JobResponse = serde_json::from_str(SHARP_QUERY_JOBS).unwrap();
+        let artifacts = QueryArtifacts::from_jobs(&jobs);
+        assert_eq!(
+            artifacts.proof_path.as_deref(),
+            Some("sharp_queries/query_01JDKQF9VY2NDBFZAFNFXZC17Z/proof.json")
+        );
+        assert_eq!(artifacts.layout, Some(Layout::RecursiveWithPoseidon));
+        assert_eq!(artifacts.cairo_version, Some(CairoVersion::Zero));
+    }
+
+    #[tokio::test]
+    async fn test_resubmit_keeps_external_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger = Ledger::open(dir.path().join("ledger.jsonl")).await.unwrap();
+        let query = query_with(|query| query["externalId"] = json!("block-42"));
+        let sdk = mock_sdk(query, ledger.clone()).await;
+        let response = sdk
+            .resubmit(QUERY_ID, QueryStep::FactHashGeneration)
+            .await
+            .unwrap();
+        let entry = ledger.get(&response.atlantic_query_id).await.unwrap();
+        assert_eq!(entry.endpoint, "/v1/l2/atlantic-query/proof-verification");
+        assert_eq!(entry.external_id, "block-42");
+        assert_eq!(entry.resubmitted_from.as_deref(), Some(QUERY_ID));
+        assert_eq!(ledger.find_resubmissions(QUERY_ID).await, vec![entry]);
+    }
+
+    #[tokio::test]
+    async fn test_resubmit_links_missing_external_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger = Ledger::open(dir.path().join("ledger.jsonl")).await.unwrap();
+        let query = query_with(|query| query["externalId"] = json!(""));
+        let sdk = mock_sdk(query, ledger.clone()).await;
+        let response = sdk
+            .resubmit(QUERY_ID, QueryStep::ProofVerification)
+            .await
+            .unwrap();
+        let entry = ledger.get(&response.atlantic_query_id).await.unwrap();
+        assert_eq!(entry.external_id, format!("resubmit:{}", QUERY_ID));
+    }
+
+    #[tokio::test]
+    async fn test_resubmit_requires_program_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger = Ledger::open(dir.path().join("ledger.jsonl")).await.unwrap();
+        let query = query_with(|query| {
+            query.as_object_mut().unwrap().remove("programHash");
+            query["steps"] = json!(["TRACE_GENERATION", "PROOF_GENERATION"]);
+        });
+        let sdk = mock_sdk(query, ledger).await;
+        assert!(matches!(
+            sdk.resubmit(QUERY_ID, QueryStep::TraceGeneration).await,
+            Err(AtlanticSdkError::MissingProgramHash(id)) if id == QUERY_ID
+        ));
+
+        let l1_query = query_with(|query| query["chain"] = json!("L1"));
+        let dir = tempfile::tempdir().unwrap();
+        let ledger = Ledger::open(dir.path().join("ledger.jsonl")).await.unwrap();
+        let sdk = mock_sdk(l1_query, ledger).await;
+        assert!(sdk
+            .resubmit(QUERY_ID, QueryStep::ProofVerification)
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,210 @@
+use std::future::Future;
+
+use futures::{stream, StreamExt};
+use tracing::{info, warn};
+
+use crate::{
+    error::AtlanticSdkError,
+    ledger::Ledger,
+    models::{AtlanticSdk, SharpQueryDetails},
+    wait::WaitOptions,
+};
+
+#[derive(Debug, Default)]
+pub struct ResumeReport {
+    pub handled: Vec<String>,
+    pub failed: Vec<(String, AtlanticSdkError)>,
+}
+
+impl AtlanticSdk {
+    // Re-attaches polling to every query in `store` whose result was neither delivered nor
+    // claimed, at most `max_in_flight` at a time, and passes each final `SharpQueryDetails`
+    // to `handler`.
+    //
+    // Delivery is at least once: the entry is claimed in `store` while the handler runs, so
+    // concurrent resumes skip it, and is only marked handled after the handler succeeds. A
+    // handler that returns an error releases its claim and is retried on the next resume. If
+    // the process dies while a handler runs, reopening the ledger releases the claim and the
+    // result is delivered again, so handlers should be idempotent on `SharpQueryDetails::id`.
+    pub async fn resume_pending<F, Fut>(
+        &self,
+        store: &Ledger,
+        options: WaitOptions,
+        max_in_flight: usize,
+        handler: F,
+    ) -> ResumeReport
+    where
+        F: Fn(SharpQueryDetails) -> Fut,
+        Fut: Future<Output = anyhow::Result<()>>,
+    {
+        let entries = store.unhandled().await;
+        info!("Resuming {} unhandled queries", entries.len());
+        let options = WaitOptions {
+            cancel_on_drop: false,
+            ..options
+        };
+        let results: Vec<_> = stream::iter(entries)
+            .map(|entry| {
+                let (options, handler) = (&options, &handler);
+                async move {
+                    let id = entry.atlantic_query_id;
+                    let result = self.deliver(store, &id, options, handler).await;
+                    (id, result)
+                }
+            })
+            .buffer_unordered(max_in_flight.max(1))
+            .collect()
+            .await;
+
+        let mut report = ResumeReport::default();
+        for (id, result) in results {
+            match result {
+                Ok(true) => report.handled.push(id),
+                Ok(false) => {}
+                Err(err) => {
+                    warn!("Failed to resume sharpQueryId {}: {}", id, err);
+                    report.failed.push((id, err));
+                }
+            }
+        }
+        report
+    }
+
+    // Returns false when another resume claimed the query first.
+    async fn deliver<F, Fut>(
+        &self,
+        store: &Ledger,
+        id: &str,
+        options: &WaitOptions,
+        handler: &F,
+    ) -> Result<bool, AtlanticSdkError>
+    where
+        F: Fn(SharpQueryDetails) -> Fut,
+        Fut: Future<Output = anyhow::Result<()>>,
+    {
+        let query = self.poll_until_terminal(id, options).await?;
+        store.update(&query).await?;
+        if !store.claim(id).await? {
+            return Ok(false);
+        }
+        if let Err(err) = handler(query).await {
+            store.release(id).await?;
+            return Err(err.into());
+        }
+        store.mark_handled(id).await?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::BTreeMap,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use axum::{extract::Path, routing::get, Router};
+
+    use super::*;
+    use crate::{ledger::LedgerEntry, mock_server, models::tests::SHARP_QUERY};
+
+    const FIRST: &str = "01JDKQF9VY2NDBFZAFNFXZC17Z";
+    const SECOND: &str = "01JDKQF21NWMENSXK96E3N7CE8";
+
+    async fn sdk() -> AtlanticSdk {
+        mock_server::sdk(Router::new().route(
+            "/v1/atlantic-query/{id}",
+            get(|Path(id): Path<String>| async move { SHARP_QUERY.replace(FIRST, &id) }),
+        ))
+        .await
+    }
+
+    async fn ledger(path: &std::path::Path, ids: &[&str]) -> Ledger {
+        let ledger = Ledger::open(path).await.unwrap();
+        for id in ids {
+            ledger
+                .record(LedgerEntry::submitted(
+                    id,
+                    "/v1/proof-generation",
+                    BTreeMap::new(),
+                ))
+                .await
+                .unwrap();
+        }
+        ledger
+    }
+
+    fn options() -> WaitOptions {
+        WaitOptions {
+            poll_interval: Duration::from_millis(10),
+            ..WaitOptions::default()
+        }
+    }
+
+    async fn resume(sdk: &AtlanticSdk, ledger: &Ledger) -> (ResumeReport, Vec<String>) {
+        let delivered = Arc::new(Mutex::new(Vec::new()));
+        let report = sdk
+            .resume_pending(ledger, options(), 4, |query| {
+                let delivered = delivered.clone();
+                async move {
+                    delivered.lock().unwrap().push(query.id);
+                    Ok(())
+                }
+            })
+            .await;
+        let delivered = delivered.lock().unwrap().clone();
+        (report, delivered)
+    }
+
+    #[tokio::test]
+    async fn test_resume_skips_queries_already_waited_for() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger = ledger(&dir.path().join("ledger.jsonl"), &[FIRST, SECOND]).await;
+        let sdk = sdk().await.with_ledger(ledger.clone());
+        sdk.wait_for_query(FIRST, options()).await.unwrap();
+
+        let (report, delivered) = resume(&sdk, &ledger).await;
+        assert_eq!(delivered, [SECOND]);
+        assert_eq!(report.handled, [SECOND]);
+        let (_, delivered) = resume(&sdk, &ledger).await;
+        assert!(delivered.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_resume_after_crash_while_handling() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger.jsonl");
+        let ledger = ledger(&path, &[FIRST]).await;
+        // The process died after claiming the query, while its handler was running.
+        assert!(ledger.claim(FIRST).await.unwrap());
+        let (_, delivered) = resume(&sdk().await, &ledger).await;
+        assert!(delivered.is_empty());
+
+        let ledger = Ledger::open(&path).await.unwrap();
+        assert!(ledger.claimed().await.is_empty());
+        let sdk = sdk().await;
+        let (_, delivered) = resume(&sdk, &ledger).await;
+        assert_eq!(delivered, [FIRST]);
+        assert!(ledger.claimed().await.is_empty());
+        let (_, delivered) = resume(&sdk, &ledger).await;
+        assert!(delivered.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failed_handler_is_retried() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger = ledger(&dir.path().join("ledger.jsonl"), &[FIRST]).await;
+        let sdk = sdk().await;
+        let report = sdk
+            .resume_pending(&ledger, options(), 4, |_| async {
+                Err(anyhow::anyhow!("downstream unavailable"))
+            })
+            .await;
+        assert_eq!(report.failed.len(), 1);
+        assert!(ledger.claimed().await.is_empty());
+
+        let (_, delivered) = resume(&sdk, &ledger).await;
+        assert_eq!(delivered, [FIRST]);
+    }
+}
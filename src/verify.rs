@@ -0,0 +1,162 @@
+use starknet_types_core::felt::Felt as StarkFelt;
+use swiftness::TransformTo;
+use swiftness_air::layout::recursive_with_poseidon;
+use swiftness_stark::types::StarkProof;
+use tracing::info;
+
+use crate::{
+    error::AtlanticSdkError,
+    felt::{short_string, Felt},
+    models::AtlanticSdk,
+};
+
+// swiftness only verifies the layout it is compiled for, the one Atlantic proves with.
+pub const SUPPORTED_LAYOUT: &str = "recursive_with_poseidon";
+// The lowest security level the Integrity verifier accepts.
+pub const DEFAULT_MIN_SECURITY_BITS: u32 = 50;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifiedProof {
+    pub layout: String,
+    // Hash of the proven program. For Atlantic proofs this is the bootloader, whose output
+    // holds the hashes of the programs it ran.
+    pub program_hash: Felt,
+    pub program_output: Vec<Felt>,
+}
+
+// Checks proofs off-chain with the swiftness verifier before paying for on-chain verification.
+#[derive(Debug, Clone, Default)]
+pub struct LocalVerifier {
+    // Lowest accepted security level, `DEFAULT_MIN_SECURITY_BITS` when unset.
+    min_security_bits: Option<u32>,
+}
+
+impl LocalVerifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_min_security_bits(mut self, min_security_bits: u32) -> Self {
+        self.min_security_bits = Some(min_security_bits);
+        self
+    }
+
+    // `proof_json` is the proof.json returned by `get_proof`.
+    pub fn verify(&self, proof_json: &str) -> Result<VerifiedProof, AtlanticSdkError> {
+        let proof = swiftness::parse(proof_json)
+            .map_err(|err| AtlanticSdkError::InvalidProof(err.to_string()))?
+            .transform_to();
+        self.verify_stark_proof(&proof)
+    }
+
+    fn verify_stark_proof(&self, proof: &StarkProof) -> Result<VerifiedProof, AtlanticSdkError> {
+        let security_bits =
+            self.check_settings(&proof.public_input.layout, proof.config.security_bits())?;
+        let (program_hash, program_output) = proof
+            .verify::<recursive_with_poseidon::Layout>(security_bits)
+            .map_err(|err| AtlanticSdkError::VerificationFailed(err.to_string()))?;
+        let verified = VerifiedProof {
+            layout: SUPPORTED_LAYOUT.to_string(),
+            program_hash: from_stark_felt(&program_hash)?,
+            program_output: program_output
+                .iter()
+                .map(from_stark_felt)
+                .collect::<Result<_, _>>()?,
+        };
+        info!(
+            "Proof of program {} verified locally",
+            verified.program_hash
+        );
+        Ok(verified)
+    }
+
+    // Returns the security level to verify the proof against.
+    fn check_settings(
+        &self,
+        layout: &StarkFelt,
+        proof_security_bits: StarkFelt,
+    ) -> Result<StarkFelt, AtlanticSdkError> {
+        if *layout != to_stark_felt(&short_string(SUPPORTED_LAYOUT)?) {
+            return Err(AtlanticSdkError::InvalidProof(format!(
+                "only {} layout proofs can be verified locally",
+                SUPPORTED_LAYOUT
+            )));
+        }
+        let min_security_bits = self.min_security_bits.unwrap_or(DEFAULT_MIN_SECURITY_BITS);
+        if proof_security_bits < StarkFelt::from(min_security_bits) {
+            return Err(AtlanticSdkError::VerificationFailed(format!(
+                "proof has {} security bits, at least {} required",
+                proof_security_bits, min_security_bits
+            )));
+        }
+        Ok(StarkFelt::from(min_security_bits))
+    }
+}
+
+pub(crate) fn to_stark_felt(felt: &Felt) -> StarkFelt {
+    StarkFelt::from_bytes_le(&felt.to_bytes_le())
+}
+
+pub(crate) fn from_stark_felt(felt: &StarkFelt) -> Result<Felt, AtlanticSdkError> {
+    Felt::from_bytes_le(&felt.to_bytes_le())
+}
+
+impl AtlanticSdk {
+    pub async fn verify_proof_locally(
+        &self,
+        query_id: String,
+        verifier: &LocalVerifier,
+    ) -> Result<VerifiedProof, AtlanticSdkError> {
+        let proof_json = self.get_proof(query_id).await?;
+        let verifier = verifier.clone();
+        tokio::task::spawn_blocking(move || verifier.verify(&proof_json))
+            .await
+            .map_err(|err| AtlanticSdkError::VerificationFailed(err.to_string()))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof::tests::PROOF;
+
+    fn layout(name: &str) -> StarkFelt {
+        to_stark_felt(&short_string(name).unwrap())
+    }
+
+    #[test]
+    fn test_check_settings() {
+        let verifier = LocalVerifier::new();
+        assert_eq!(
+            verifier
+                .check_settings(&layout(SUPPORTED_LAYOUT), StarkFelt::from(96))
+                .unwrap(),
+            StarkFelt::from(DEFAULT_MIN_SECURITY_BITS)
+        );
+        assert!(matches!(
+            verifier.check_settings(&layout("recursive"), StarkFelt::from(96)),
+            Err(AtlanticSdkError::InvalidProof(_))
+        ));
+        // Weak proofs fail without a configured minimum.
+        assert!(matches!(
+            verifier.check_settings(&layout(SUPPORTED_LAYOUT), StarkFelt::from(40)),
+            Err(AtlanticSdkError::VerificationFailed(_))
+        ));
+        // Asking for more security than the proof was generated with.
+        assert!(matches!(
+            verifier
+                .with_min_security_bits(200)
+                .check_settings(&layout(SUPPORTED_LAYOUT), StarkFelt::from(96)),
+            Err(AtlanticSdkError::VerificationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_rejects_invalid_proofs() {
+        // The fixture proof has no annotations to read the commitments from.
+        assert!(matches!(
+            LocalVerifier::new().verify(PROOF),
+            Err(AtlanticSdkError::InvalidProof(_))
+        ));
+    }
+}
@@ -0,0 +1,179 @@
+use std::time::{Duration, Instant};
+
+use tracing::{info, warn};
+
+use crate::{
+    error::AtlanticSdkError,
+    models::{AtlanticSdk, SharpQueryDetails},
+};
+
+#[derive(Debug, Clone)]
+pub struct WaitOptions {
+    pub poll_interval: Duration,
+    pub timeout: Option<Duration>,
+    // Cancel the query on Atlantic when the wait future is dropped before it finishes.
+    pub cancel_on_drop: bool,
+}
+
+impl Default for WaitOptions {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(10),
+            timeout: None,
+            cancel_on_drop: false,
+        }
+    }
+}
+
+struct CancelOnDrop {
+    sdk: AtlanticSdk,
+    query_id: String,
+    armed: bool,
+}
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            warn!(
+                "No runtime available to cancel sharpQueryId: {}",
+                self.query_id
+            );
+            return;
+        };
+        let sdk = self.sdk.clone();
+        let query_id = std::mem::take(&mut self.query_id);
+        handle.spawn(async move {
+            if let Err(err) = sdk.cancel_query(&query_id).await {
+                warn!("Failed to cancel sharpQueryId {}: {}", query_id, err);
+            }
+        });
+    }
+}
+
+impl AtlanticSdk {
+    // Polls `get_sharp_query` until the query reaches a terminal status. With a ledger
+    // configured, the returned result counts as delivered, so `resume_pending` skips it.
+    pub async fn wait_for_query(
+        &self,
+        sharp_query_id: &str,
+        options: WaitOptions,
+    ) -> Result<SharpQueryDetails, AtlanticSdkError> {
+        let mut guard = CancelOnDrop {
+            sdk: self.clone(),
+            query_id: sharp_query_id.to_string(),
+            armed: options.cancel_on_drop,
+        };
+        let result = self.poll_until_terminal(sharp_query_id, &options).await;
+        guard.armed = false;
+        if let (Ok(_), Some(ledger)) = (&result, &self.ledger) {
+            if let Err(err) = ledger.mark_handled(sharp_query_id).await {
+                warn!(
+                    "Failed to mark sharpQueryId {} handled in ledger: {}",
+                    sharp_query_id, err
+                );
+            }
+        }
+        result
+    }
+
+    pub(crate) async fn poll_until_terminal(
+        &self,
+        sharp_query_id: &str,
+        options: &WaitOptions,
+    ) -> Result<SharpQueryDetails, AtlanticSdkError> {
+        let start = Instant::now();
+        loop {
+            let query = self.get_sharp_query(sharp_query_id).await?.sharp_query;
+            if query.status.is_terminal() {
+                info!(
+                    "sharpQueryId {} finished with status {}",
+                    sharp_query_id, query.status
+                );
+                return Ok(query);
+            }
+            if let Some(timeout) = options.timeout {
+                if start.elapsed() >= timeout {
+                    return Err(AtlanticSdkError::Timeout(sharp_query_id.to_string()));
+                }
+            }
+            tokio::time::sleep(options.poll_interval).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use axum::{
+        routing::{get, post},
+        Router,
+    };
+
+    use super::*;
+    use crate::mock_server;
+
+    const RUNNING_QUERY: &str = r#"{
+        "atlanticQuery": {
+            "id": "01JDKQF9VY2NDBFZAFNFXZC17Z",
+            "submittedByClient": "01J8M351V7NV7QRQYCS54RKFYD",
+            "status": "IN_PROGRESS",
+            "price": "0",
+            "gasUsed": 0,
+            "creditsUsed": 0,
+            "traceCreditsUsed": 0,
+            "steps": [],
+            "createdAt": "2024-11-26T07:55:21.124Z"
+        }
+    }"#;
+
+    #[tokio::test]
+    async fn test_cancel_on_drop() {
+        let cancels = Arc::new(AtomicUsize::new(0));
+        let counter = cancels.clone();
+        let sdk = mock_server::sdk(
+            Router::new()
+                .route("/v1/atlantic-query/{id}", get(|| async { RUNNING_QUERY }))
+                .route(
+                    "/v1/atlantic-query-cancel/{id}",
+                    post(move || async move {
+                        counter.fetch_add(1, Ordering::SeqCst);
+                    }),
+                ),
+        )
+        .await;
+        let options = WaitOptions {
+            poll_interval: Duration::from_millis(10),
+            timeout: None,
+            cancel_on_drop: true,
+        };
+        let wait = sdk.wait_for_query("01JDKQF9VY2NDBFZAFNFXZC17Z", options.clone());
+        assert!(tokio::time::timeout(Duration::from_millis(50), wait)
+            .await
+            .is_err());
+        for _ in 0..100 {
+            if cancels.load(Ordering::SeqCst) > 0 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(cancels.load(Ordering::SeqCst), 1);
+
+        let options = WaitOptions {
+            cancel_on_drop: false,
+            ..options
+        };
+        let wait = sdk.wait_for_query("01JDKQF9VY2NDBFZAFNFXZC17Z", options);
+        assert!(tokio::time::timeout(Duration::from_millis(50), wait)
+            .await
+            .is_err());
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(cancels.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,446 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+
+use futures::{stream, Stream};
+use tracing::{debug, warn};
+
+use crate::{
+    error::AtlanticSdkError,
+    models::{AtlanticSdk, QueryStatus, SharpQueryDetails},
+};
+
+const DEFAULT_PAGE_SIZE: u32 = 100;
+const DEFAULT_MAX_FAILURES: u32 = 5;
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryEvent {
+    pub id: String,
+    // `None` the first time a query is observed.
+    pub old_status: Option<QueryStatus>,
+    pub new_status: QueryStatus,
+    pub step: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Observed {
+    status: Option<QueryStatus>,
+    step: Option<String>,
+    // Consecutive rounds in which the query could not be fetched.
+    failures: u32,
+}
+
+impl Observed {
+    // The event for a change since the last committed state, without recording it.
+    fn diff(&self, query: &SharpQueryDetails) -> Option<QueryEvent> {
+        if self.status == Some(query.status) && self.step == query.step {
+            return None;
+        }
+        Some(QueryEvent {
+            id: query.id.clone(),
+            old_status: self.status,
+            new_status: query.status,
+            step: query.step.clone(),
+        })
+    }
+
+    fn commit(&mut self, event: &QueryEvent) {
+        self.status = Some(event.new_status);
+        self.step = event.step.clone();
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.status.is_some_and(|status| status.is_terminal())
+    }
+}
+
+// Outcome of one polling round. Errors are `AtlanticSdkError::WatchFailed`, one per query.
+#[derive(Debug, Default)]
+pub struct PollRound {
+    pub events: Vec<QueryEvent>,
+    pub errors: Vec<AtlanticSdkError>,
+}
+
+// Tracks many queries at once. Each round lists the most recent queries with
+// `get_sharp_queries` and only falls back to `get_sharp_query` for the ids not on that page.
+// A query that fails `max_failures` rounds in a row is dropped from polling.
+// `into_stream` applies the same limit to the listing.
+#[derive(Debug, Clone)]
+pub struct AtlanticWatcher {
+    sdk: AtlanticSdk,
+    queries: HashMap<String, Observed>,
+    poll_interval: Duration,
+    page_size: u32,
+    max_failures: u32,
+}
+
+impl AtlanticWatcher {
+    pub fn new<I, S>(sdk: AtlanticSdk, query_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            sdk,
+            queries: query_ids
+                .into_iter()
+                .map(|id| (id.into(), Observed::default()))
+                .collect(),
+            poll_interval: Duration::from_secs(10),
+            page_size: DEFAULT_PAGE_SIZE,
+            max_failures: DEFAULT_MAX_FAILURES,
+        }
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    pub fn with_page_size(mut self, page_size: u32) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    pub fn with_max_failures(mut self, max_failures: u32) -> Self {
+        self.max_failures = max_failures.max(1);
+        self
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.queries
+            .values()
+            .all(|observed| self.is_settled(observed))
+    }
+
+    fn is_settled(&self, observed: &Observed) -> bool {
+        observed.is_terminal() || observed.failures >= self.max_failures
+    }
+
+    // Polls once and returns the changes since the previous round. Only a failed listing
+    // fails the round; queries that cannot be fetched are reported in `PollRound::errors`.
+    pub async fn poll(&mut self) -> Result<PollRound, AtlanticSdkError> {
+        let round = self.poll_round().await?;
+        for event in &round.events {
+            self.commit(event);
+        }
+        Ok(round)
+    }
+
+    // Fetches every pending query. Failure counts are updated here, statuses only once the
+    // event is handed out through `commit`.
+    async fn poll_round(&mut self) -> Result<PollRound, AtlanticSdkError> {
+        let mut pending: Vec<String> = self
+            .queries
+            .iter()
+            .filter(|(_, observed)| !self.is_settled(observed))
+            .map(|(id, _)| id.clone())
+            .collect();
+        pending.sort();
+        let mut round = PollRound::default();
+        if pending.is_empty() {
+            return Ok(round);
+        }
+
+        let page = self
+            .sdk
+            .get_sharp_queries(Some(self.page_size), None)
+            .await?;
+        let mut listed: HashMap<String, SharpQueryDetails> = page
+            .sharp_queries
+            .into_iter()
+            .map(|query| (query.id.clone(), query))
+            .collect();
+
+        for id in pending {
+            let query = match listed.remove(&id) {
+                Some(query) => Ok(query),
+                None => {
+                    debug!("sharpQueryId {} not in listing, fetching directly", id);
+                    self.sdk
+                        .get_sharp_query(&id)
+                        .await
+                        .map(|response| response.sharp_query)
+                }
+            };
+            let max_failures = self.max_failures;
+            let Some(observed) = self.queries.get_mut(&id) else {
+                continue;
+            };
+            match query {
+                Ok(query) => {
+                    observed.failures = 0;
+                    round.events.extend(observed.diff(&query));
+                }
+                Err(err) => {
+                    observed.failures += 1;
+                    let gave_up = observed.failures >= max_failures;
+                    if gave_up {
+                        warn!(
+                            "Giving up on sharpQueryId {} after {} failed polls",
+                            id, observed.failures
+                        );
+                    }
+                    round.errors.push(AtlanticSdkError::WatchFailed {
+                        id,
+                        source: Box::new(err),
+                        gave_up,
+                    });
+                }
+            }
+        }
+        Ok(round)
+    }
+
+    fn commit(&mut self, event: &QueryEvent) {
+        if let Some(observed) = self.queries.get_mut(&event.id) {
+            observed.commit(event);
+        }
+    }
+
+    // Emits events until every query is terminal or given up on. An event is only recorded as
+    // seen once the stream yields it. Per-query errors are yielded and polling continues. A
+    // failed listing is yielded and retried with exponential backoff; the stream ends after it
+    // fails `max_failures` times in a row or with an error retrying cannot fix, such as
+    // `Unauthorized`.
+    pub fn into_stream(self) -> impl Stream<Item = Result<QueryEvent, AtlanticSdkError>> {
+        let state = StreamState {
+            watcher: self,
+            buffered: VecDeque::new(),
+            first: true,
+            listing_failures: 0,
+            finished: false,
+        };
+        stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(item) = state.buffered.pop_front() {
+                    if let Ok(event) = &item {
+                        state.watcher.commit(event);
+                    }
+                    return Some((item, state));
+                }
+                if state.finished || state.watcher.is_done() {
+                    return None;
+                }
+                if !state.first {
+                    tokio::time::sleep(state.watcher.backoff(state.listing_failures)).await;
+                }
+                state.first = false;
+                match state.watcher.poll_round().await {
+                    Ok(round) => {
+                        state.listing_failures = 0;
+                        state.buffered.extend(round.events.into_iter().map(Ok));
+                        state.buffered.extend(round.errors.into_iter().map(Err));
+                    }
+                    Err(err) => {
+                        state.listing_failures += 1;
+                        if is_fatal(&err) || state.listing_failures >= state.watcher.max_failures {
+                            warn!("Stopped watching queries: {}", err);
+                            state.finished = true;
+                        } else {
+                            warn!("Failed to poll watched queries: {}", err);
+                        }
+                        return Some((Err(err), state));
+                    }
+                }
+            }
+        })
+    }
+
+    // Poll interval doubled for each consecutive failed listing, up to `MAX_BACKOFF`.
+    fn backoff(&self, listing_failures: u32) -> Duration {
+        self.poll_interval
+            .saturating_mul(2u32.saturating_pow(listing_failures))
+            .min(MAX_BACKOFF.max(self.poll_interval))
+    }
+}
+
+struct StreamState {
+    watcher: AtlanticWatcher,
+    buffered: VecDeque<Result<QueryEvent, AtlanticSdkError>>,
+    first: bool,
+    // Consecutive rounds in which the listing itself failed.
+    listing_failures: u32,
+    finished: bool,
+}
+
+// Errors that polling again will not fix.
+fn is_fatal(err: &AtlanticSdkError) -> bool {
+    matches!(
+        err,
+        AtlanticSdkError::Unauthorized(_) | AtlanticSdkError::InsufficientCredits(_)
+    )
+}
+
+impl AtlanticSdk {
+    pub fn watch<I, S>(&self, query_ids: I) -> AtlanticWatcher
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        AtlanticWatcher::new(self.clone(), query_ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{http::StatusCode, routing::get, Router};
+    use futures::StreamExt;
+
+    use super::*;
+    use crate::{
+        mock_server,
+        models::tests::{sharp_query, SHARP_QUERIES},
+    };
+
+    fn details(status: QueryStatus, step: Option<&str>) -> SharpQueryDetails {
+        let mut query = sharp_query();
+        query.status = status;
+        query.step = step.map(str::to_string);
+        query
+    }
+
+    fn update(observed: &mut Observed, query: &SharpQueryDetails) -> Option<QueryEvent> {
+        let event = observed.diff(query)?;
+        observed.commit(&event);
+        Some(event)
+    }
+
+    #[test]
+    fn test_observed_update() {
+        let mut observed = Observed::default();
+        let event = update(
+            &mut observed,
+            &details(QueryStatus::InProgress, Some("TRACE_GENERATION")),
+        )
+        .unwrap();
+        assert_eq!(event.old_status, None);
+        assert_eq!(event.new_status, QueryStatus::InProgress);
+        assert!(update(
+            &mut observed,
+            &details(QueryStatus::InProgress, Some("TRACE_GENERATION"))
+        )
+        .is_none());
+        let event = update(
+            &mut observed,
+            &details(QueryStatus::InProgress, Some("PROOF_GENERATION")),
+        )
+        .unwrap();
+        assert_eq!(event.step.as_deref(), Some("PROOF_GENERATION"));
+        let event = update(
+            &mut observed,
+            &details(QueryStatus::Done, Some("PROOF_GENERATION")),
+        )
+        .unwrap();
+        assert_eq!(event.old_status, Some(QueryStatus::InProgress));
+        assert!(observed.is_terminal());
+    }
+
+    async fn watcher() -> AtlanticWatcher {
+        let sdk = mock_server::sdk(
+            Router::new()
+                .route("/v1/atlantic-queries", get(|| async { SHARP_QUERIES }))
+                .route(
+                    "/v1/atlantic-query/{id}",
+                    get(|| async { (StatusCode::NOT_FOUND, "not found") }),
+                ),
+        )
+        .await;
+        sdk.watch(["01JDKQF9VY2NDBFZAFNFXZC17Z", "missing"])
+            .with_poll_interval(Duration::from_millis(10))
+            .with_max_failures(2)
+    }
+
+    #[tokio::test]
+    async fn test_poll_reports_failures_per_query() {
+        let mut watcher = watcher().await;
+        let round = watcher.poll().await.unwrap();
+        assert_eq!(round.events.len(), 1);
+        assert_eq!(round.events[0].id, "01JDKQF9VY2NDBFZAFNFXZC17Z");
+        assert!(matches!(
+            &round.errors[..],
+            [AtlanticSdkError::WatchFailed { id, gave_up: false, .. }] if id == "missing"
+        ));
+        assert!(!watcher.is_done());
+
+        let round = watcher.poll().await.unwrap();
+        assert!(round.events.is_empty());
+        assert!(matches!(
+            &round.errors[..],
+            [AtlanticSdkError::WatchFailed { gave_up: true, .. }]
+        ));
+        assert!(watcher.is_done());
+    }
+
+    #[tokio::test]
+    async fn test_events_committed_when_yielded() {
+        let mut watcher = watcher().await;
+        let round = watcher.poll_round().await.unwrap();
+        assert_eq!(round.events.len(), 1);
+        // Not handed out yet, so the next round reports the same change.
+        let round = watcher.poll_round().await.unwrap();
+        assert_eq!(round.events.len(), 1);
+
+        // The query that failed twice is given up on; the change is still delivered once.
+        let items: Vec<_> = watcher.into_stream().collect().await;
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].as_ref().unwrap().new_status, QueryStatus::Done);
+    }
+
+    async fn failing_watcher(status: StatusCode) -> AtlanticWatcher {
+        let sdk = mock_server::sdk(Router::new().route(
+            "/v1/atlantic-queries",
+            get(move || async move { (status, "error") }),
+        ))
+        .await;
+        sdk.watch(["01JDKQF9VY2NDBFZAFNFXZC17Z"])
+            .with_poll_interval(Duration::from_millis(1))
+            .with_max_failures(3)
+    }
+
+    #[tokio::test]
+    async fn test_stream_ends_on_listing_failures() {
+        let items: Vec<_> = failing_watcher(StatusCode::UNAUTHORIZED)
+            .await
+            .into_stream()
+            .collect()
+            .await;
+        assert!(matches!(
+            &items[..],
+            [Err(AtlanticSdkError::Unauthorized(_))]
+        ));
+
+        let items: Vec<_> = failing_watcher(StatusCode::BAD_GATEWAY)
+            .await
+            .into_stream()
+            .collect()
+            .await;
+        assert_eq!(items.len(), 3);
+        assert!(items.iter().all(|item| item.is_err()));
+    }
+
+    #[test]
+    fn test_backoff_is_capped() {
+        let watcher = AtlanticWatcher::new(
+            AtlanticSdk::new(
+                "api_key".to_string(),
+                url::Url::parse("http://localhost").unwrap(),
+            )
+            .unwrap(),
+            ["id"],
+        )
+        .with_poll_interval(Duration::from_secs(10));
+        assert_eq!(watcher.backoff(0), Duration::from_secs(10));
+        assert_eq!(watcher.backoff(2), Duration::from_secs(40));
+        assert_eq!(watcher.backoff(40), MAX_BACKOFF);
+    }
+
+    #[tokio::test]
+    async fn test_stream_ends_after_giving_up() {
+        let items: Vec<_> = watcher().await.into_stream().collect().await;
+        assert_eq!(items.iter().filter(|item| item.is_ok()).count(), 1);
+        assert_eq!(items.iter().filter(|item| item.is_err()).count(), 2);
+    }
+}
@@ -0,0 +1,301 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    routing::post,
+    Router,
+};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+
+use crate::{
+    error::AtlanticSdkError,
+    models::{SharpQueryDetails, SharpQueryResponse},
+};
+
+pub const SIGNATURE_HEADER: &str = "x-atlantic-signature";
+pub const TIMESTAMP_HEADER: &str = "x-atlantic-timestamp";
+const CHANNEL_CAPACITY: usize = 1024;
+
+// What the HMAC-SHA256 signature covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureScheme {
+    // The raw body.
+    Body,
+    // `<timestamp>.<raw body>`, with the unix timestamp (seconds) from the timestamp header.
+    TimestampedBody,
+}
+
+// Atlantic does not publish its callback signing scheme, so the header names and the signed
+// payload are configurable and must match how the callback secret is set up on the server.
+// With `TimestampedBody`, callbacks older or newer than `tolerance` are rejected, and a
+// signature is only accepted once within that window.
+#[derive(Debug, Clone)]
+pub struct WebhookConfig {
+    pub signature_header: String,
+    pub timestamp_header: String,
+    // Stripped from the signature header value before hex decoding, if present.
+    pub signature_prefix: String,
+    pub scheme: SignatureScheme,
+    pub tolerance: Duration,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            signature_header: SIGNATURE_HEADER.to_string(),
+            timestamp_header: TIMESTAMP_HEADER.to_string(),
+            signature_prefix: "sha256=".to_string(),
+            scheme: SignatureScheme::TimestampedBody,
+            tolerance: Duration::from_secs(300),
+        }
+    }
+}
+
+// Receives Atlantic completion callbacks and fans them out to subscribers.
+#[derive(Clone)]
+pub struct WebhookReceiver {
+    secret: Vec<u8>,
+    config: WebhookConfig,
+    // Signatures accepted within the tolerance window, with their timestamps.
+    seen: Arc<Mutex<HashMap<Vec<u8>, i64>>>,
+    sender: broadcast::Sender<SharpQueryDetails>,
+}
+
+// The secret is left out so receivers can be logged safely.
+impl std::fmt::Debug for WebhookReceiver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookReceiver")
+            .field("secret", &"<redacted>")
+            .field("config", &self.config)
+            .field("subscribers", &self.sender.receiver_count())
+            .finish()
+    }
+}
+
+impl WebhookReceiver {
+    pub fn new(secret: impl Into<Vec<u8>>) -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self {
+            secret: secret.into(),
+            config: WebhookConfig::default(),
+            seen: Arc::default(),
+            sender,
+        }
+    }
+
+    pub fn with_config(mut self, config: WebhookConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<SharpQueryDetails> {
+        self.sender.subscribe()
+    }
+
+    // Mount with e.g. `Router::new().merge(receiver.router("/atlantic/callback"))`.
+    pub fn router<S>(&self, path: &str) -> Router<S> {
+        Router::new()
+            .route(path, post(handle_callback))
+            .with_state(self.clone())
+    }
+
+    // Checks the hex encoded HMAC-SHA256 of `payload`, see `SignatureScheme`.
+    pub fn verify_signature(&self, payload: &[u8], signature: &str) -> bool {
+        decode_signature(signature, &self.config.signature_prefix)
+            .is_some_and(|signature| self.verify_mac(payload, &signature))
+    }
+
+    pub fn parse_event(
+        &self,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> Result<SharpQueryDetails, AtlanticSdkError> {
+        let signature = header(headers, &self.config.signature_header)
+            .and_then(|signature| decode_signature(signature, &self.config.signature_prefix))
+            .ok_or(AtlanticSdkError::InvalidWebhookSignature)?;
+        match self.config.scheme {
+            SignatureScheme::Body => {
+                if !self.verify_mac(body, &signature) {
+                    return Err(AtlanticSdkError::InvalidWebhookSignature);
+                }
+            }
+            SignatureScheme::TimestampedBody => {
+                let timestamp: i64 = header(headers, &self.config.timestamp_header)
+                    .and_then(|timestamp| timestamp.parse().ok())
+                    .ok_or(AtlanticSdkError::InvalidWebhookSignature)?;
+                let mut payload = format!("{}.", timestamp).into_bytes();
+                payload.extend_from_slice(body);
+                if !self.verify_mac(&payload, &signature) {
+                    return Err(AtlanticSdkError::InvalidWebhookSignature);
+                }
+                self.check_replay(signature, timestamp)?;
+            }
+        }
+        match serde_json::from_slice::<SharpQueryResponse>(body) {
+            Ok(response) => Ok(response.sharp_query),
+            Err(_) => Ok(serde_json::from_slice::<SharpQueryDetails>(body)?),
+        }
+    }
+
+    fn verify_mac(&self, payload: &[u8], signature: &[u8]) -> bool {
+        let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(&self.secret) else {
+            return false;
+        };
+        mac.update(payload);
+        mac.verify_slice(signature).is_ok()
+    }
+
+    fn check_replay(&self, signature: Vec<u8>, timestamp: i64) -> Result<(), AtlanticSdkError> {
+        let now = Utc::now().timestamp();
+        let tolerance = self.config.tolerance.as_secs() as i64;
+        if (now - timestamp).abs() > tolerance {
+            return Err(AtlanticSdkError::InvalidWebhookSignature);
+        }
+        let mut seen = self.seen.lock().expect("webhook replay cache poisoned");
+        seen.retain(|_, seen_at| (now - *seen_at).abs() <= tolerance);
+        if seen.insert(signature, timestamp).is_some() {
+            return Err(AtlanticSdkError::InvalidWebhookSignature);
+        }
+        Ok(())
+    }
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+fn decode_signature(signature: &str, prefix: &str) -> Option<Vec<u8>> {
+    hex::decode(signature.strip_prefix(prefix).unwrap_or(signature)).ok()
+}
+
+async fn handle_callback(
+    State(receiver): State<WebhookReceiver>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> StatusCode {
+    match receiver.parse_event(&headers, &body) {
+        Ok(query) => {
+            info!(
+                "Received callback for sharpQueryId {} with status {}",
+                query.id, query.status
+            );
+            // No subscribers is not an error for the sender.
+            let _ = receiver.sender.send(query);
+            StatusCode::OK
+        }
+        Err(AtlanticSdkError::InvalidWebhookSignature) => {
+            warn!("Rejected callback with invalid signature");
+            StatusCode::UNAUTHORIZED
+        }
+        Err(err) => {
+            warn!("Rejected malformed callback: {}", err);
+            StatusCode::BAD_REQUEST
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BODY: &[u8] = br#"{
+        "atlanticQuery": {
+            "id": "01JDKQF9VY2NDBFZAFNFXZC17Z",
+            "submittedByClient": "01J8M351V7NV7QRQYCS54RKFYD",
+            "status": "DONE",
+            "price": "0.85",
+            "gasUsed": 14605,
+            "creditsUsed": 0,
+            "traceCreditsUsed": 0,
+            "steps": [],
+            "createdAt": "2024-11-26T07:55:21.124Z"
+        }
+    }"#;
+
+    fn sign(secret: &[u8], payload: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret).unwrap();
+        mac.update(payload);
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    fn signed_headers(secret: &[u8], timestamp: i64) -> HeaderMap {
+        let mut payload = format!("{}.", timestamp).into_bytes();
+        payload.extend_from_slice(BODY);
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            SIGNATURE_HEADER,
+            format!("sha256={}", sign(secret, &payload))
+                .parse()
+                .unwrap(),
+        );
+        headers.insert(TIMESTAMP_HEADER, timestamp.to_string().parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_parse_event() {
+        let receiver = WebhookReceiver::new("secret");
+        let now = Utc::now().timestamp();
+        let headers = signed_headers(b"secret", now);
+        let query = receiver.parse_event(&headers, BODY).unwrap();
+        assert_eq!(query.id, "01JDKQF9VY2NDBFZAFNFXZC17Z");
+
+        for headers in [
+            signed_headers(b"other", now - 1),
+            signed_headers(b"secret", now - 3600),
+            HeaderMap::new(),
+        ] {
+            assert!(matches!(
+                receiver.parse_event(&headers, BODY),
+                Err(AtlanticSdkError::InvalidWebhookSignature)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_replayed_event() {
+        let receiver = WebhookReceiver::new("secret");
+        let headers = signed_headers(b"secret", Utc::now().timestamp());
+        assert!(receiver.parse_event(&headers, BODY).is_ok());
+        assert!(matches!(
+            receiver.parse_event(&headers, BODY),
+            Err(AtlanticSdkError::InvalidWebhookSignature)
+        ));
+    }
+
+    #[test]
+    fn test_debug_redacts_secret() {
+        let debug = format!("{:?}", WebhookReceiver::new("hunter2"));
+        assert!(debug.contains("<redacted>"));
+        assert!(!debug.contains("hunter2"));
+        assert!(!debug.contains(&format!("{:?}", b"hunter2".to_vec())));
+    }
+
+    #[test]
+    fn test_custom_config() {
+        let receiver = WebhookReceiver::new("secret").with_config(WebhookConfig {
+            signature_header: "x-signature".to_string(),
+            signature_prefix: String::new(),
+            scheme: SignatureScheme::Body,
+            ..WebhookConfig::default()
+        });
+        let mut headers = HeaderMap::new();
+        headers.insert("x-signature", sign(b"secret", BODY).parse().unwrap());
+        assert!(receiver.parse_event(&headers, BODY).is_ok());
+        assert!(receiver.verify_signature(BODY, &sign(b"secret", BODY)));
+
+        headers.insert(SIGNATURE_HEADER, sign(b"secret", BODY).parse().unwrap());
+        headers.remove("x-signature");
+        assert!(receiver.parse_event(&headers, BODY).is_err());
+    }
+}